@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use crate::types::{
+    ClientFramework, EnvVarUsage, Issue, IssueKind, Language, Location, ProjectInfo, Severity,
+};
+
+/// Find vars read in client-side code without the prefix the framework needs
+/// to expose them. Vite, Create React App and Next.js replace unprefixed
+/// reads with `undefined` in the browser bundle, so these fail silently.
+pub fn find_client_prefix_issues(usages: &[EnvVarUsage], project: &ProjectInfo) -> Vec<Issue> {
+    let mut unexposed: BTreeMap<&str, (ClientFramework, Vec<Location>)> = BTreeMap::new();
+
+    for usage in usages {
+        // Framework env modules already declare their own exposure
+        if usage.exposure.is_some()
+            || !matches!(usage.language, Language::JavaScript | Language::TypeScript)
+        {
+            continue;
+        }
+        let Some(app) = project.client_app_for(&usage.file_path) else {
+            continue;
+        };
+
+        // import.meta.env only exposes Vite's prefix, on the server too
+        let reads_import_meta = usage
+            .context
+            .as_deref()
+            .is_some_and(|c| c.contains("import.meta.env"));
+        let frameworks: &[ClientFramework] =
+            if reads_import_meta && app.frameworks.contains(&ClientFramework::Vite) {
+                &[ClientFramework::Vite]
+            } else if project.client_files.contains(&usage.file_path) {
+                &app.frameworks
+            } else {
+                continue;
+            };
+
+        if frameworks.iter().any(|f| is_exposed(*f, &usage.name)) {
+            continue;
+        }
+        unexposed
+            .entry(&usage.name)
+            .or_insert_with(|| (frameworks[0], Vec::new()))
+            .1
+            .push(Location {
+                file: usage.file_path.clone(),
+                line: Some(usage.line),
+                column: Some(usage.column),
+            });
+    }
+
+    unexposed
+        .into_iter()
+        .map(|(name, (framework, locations))| Issue {
+            kind: IssueKind::MissingClientPrefix,
+            severity: Severity::Error,
+            var_name: name.to_string(),
+            message: format!(
+                "'{}' is read in client code but {} only exposes {}* vars to the browser",
+                name,
+                framework.display_name(),
+                framework.public_prefix()
+            ),
+            locations,
+            suggestion: Some(format!(
+                "Rename to {}{}, or read it on the server",
+                framework.public_prefix(),
+                name
+            )),
+        })
+        .collect()
+}
+
+fn is_exposed(framework: ClientFramework, name: &str) -> bool {
+    name.starts_with(framework.public_prefix()) || framework.builtin_vars().contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ClientApp;
+    use std::path::{Path, PathBuf};
+
+    fn usage(name: &str, file: &str, context: &str) -> EnvVarUsage {
+        EnvVarUsage {
+            name: name.to_string(),
+            file_path: PathBuf::from(file),
+            line: 1,
+            column: 1,
+            language: Language::JavaScript,
+            context: Some(context.to_string()),
+            default_value: None,
+            required: None,
+            exposure: None,
+        }
+    }
+
+    fn project(frameworks: Vec<ClientFramework>, client_files: &[&str]) -> ProjectInfo {
+        ProjectInfo {
+            client_apps: vec![ClientApp {
+                root: PathBuf::from("web"),
+                frameworks,
+            }],
+            client_files: client_files.iter().map(PathBuf::from).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_vite_import_meta_env() {
+        let project = project(vec![ClientFramework::Vite], &[]);
+        let usages = vec![
+            usage(
+                "API_URL",
+                "web/src/api.ts",
+                "fetch(import.meta.env.API_URL)",
+            ),
+            usage(
+                "VITE_API_URL",
+                "web/src/api.ts",
+                "import.meta.env.VITE_API_URL",
+            ),
+            usage("MODE", "web/src/main.ts", "if (import.meta.env.MODE)"),
+            usage("PORT", "web/vite.config.ts", "port: process.env.PORT"),
+            usage("SECRET", "api/src/index.ts", "import.meta.env.SECRET"),
+        ];
+        let issues = find_client_prefix_issues(&usages, &project);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "API_URL");
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Rename to VITE_API_URL, or read it on the server")
+        );
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_client_files() {
+        let project = project(vec![ClientFramework::Next], &["web/app/button.tsx"]);
+        let usages = vec![
+            usage("STRIPE_KEY", "web/app/button.tsx", "process.env.STRIPE_KEY"),
+            usage(
+                "NEXT_PUBLIC_URL",
+                "web/app/button.tsx",
+                "process.env.NEXT_PUBLIC_URL",
+            ),
+            usage("NODE_ENV", "web/app/button.tsx", "process.env.NODE_ENV"),
+            usage(
+                "DATABASE_URL",
+                "web/app/page.tsx",
+                "process.env.DATABASE_URL",
+            ),
+        ];
+        let issues = find_client_prefix_issues(&usages, &project);
+        let names: Vec<_> = issues.iter().map(|i| i.var_name.as_str()).collect();
+        assert_eq!(names, vec!["STRIPE_KEY"]);
+        assert_eq!(issues[0].locations[0].file, Path::new("web/app/button.tsx"));
+    }
+}
@@ -27,8 +27,14 @@ pub fn find_missing_vars(definitions: &[EnvVarDefinition], usages: &[EnvVarUsage
             })
             .collect();
 
+        // Vars that every usage treats as optional won't break at runtime
+        let optional = usages
+            .iter()
+            .filter(|u| u.name == name)
+            .all(|u| u.is_optional());
+
         let location_count = locations.len();
-        let message = if location_count == 1 {
+        let mut message = if location_count == 1 {
             format!(
                 "'{}' is used in code but not defined in any .env file",
                 name
@@ -39,10 +45,17 @@ pub fn find_missing_vars(definitions: &[EnvVarDefinition], usages: &[EnvVarUsage
                 name, location_count
             )
         };
+        if optional {
+            message.push_str(" (optional or has a default)");
+        }
 
         issues.push(Issue {
             kind: IssueKind::MissingEnvVar,
-            severity: Severity::Error,
+            severity: if optional {
+                Severity::Info
+            } else {
+                Severity::Error
+            },
             var_name: name.to_string(),
             message,
             locations,
@@ -65,6 +78,7 @@ mod tests {
             value: Some("test".to_string()),
             source_file: PathBuf::from(".env"),
             line: 1,
+            environment: None,
         }
     }
 
@@ -76,6 +90,9 @@ mod tests {
             column: 5,
             language: Language::JavaScript,
             context: None,
+            default_value: None,
+            required: None,
+            exposure: None,
         }
     }
 
@@ -108,4 +125,15 @@ mod tests {
         let issues = find_missing_vars(&definitions, &usages);
         assert_eq!(issues.len(), 2);
     }
+
+    #[test]
+    fn test_optional_missing_var_is_info() {
+        let definitions = vec![];
+        let mut usage = make_usage("LOG_LEVEL");
+        usage.default_value = Some("info".to_string());
+
+        let issues = find_missing_vars(&definitions, &[usage]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Info);
+    }
 }
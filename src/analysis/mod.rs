@@ -1,28 +1,51 @@
+mod client;
 mod missing;
 mod naming;
+mod permissions;
+mod secrets;
+mod typos;
 mod unused;
 
+pub use client::find_client_prefix_issues;
 pub use missing::find_missing_vars;
-pub use naming::find_naming_issues;
+pub use naming::{
+    find_case_collisions, find_convention_issues, find_naming_issues, find_prefix_outliers,
+};
+pub use permissions::find_permission_issues;
+pub use secrets::{find_exposed_secrets, PUBLIC_PREFIXES};
+pub use typos::pair_typos;
 pub use unused::find_unused_vars;
 
 use crate::config::Config;
-use crate::rules::get_all_rules;
-use crate::types::{EnvVarDefinition, EnvVarUsage, Issue};
+use crate::rules::{get_all_rules, get_all_synonyms};
+use crate::types::{EnvVarDefinition, EnvVarUsage, Issue, ProjectInfo};
 
 /// Run all analyses and return combined issues
 pub fn analyze(
     definitions: &[EnvVarDefinition],
     usages: &[EnvVarUsage],
+    project: &ProjectInfo,
     config: &Config,
 ) -> Vec<Issue> {
     let mut issues = Vec::new();
 
     // Find missing env vars (used but not defined)
-    issues.extend(find_missing_vars(definitions, usages));
+    let missing = find_missing_vars(definitions, usages);
 
     // Find unused env vars (defined but not used)
-    issues.extend(find_unused_vars(definitions, usages));
+    let unused = find_unused_vars(definitions, usages);
+
+    // Merge missing/unused pairs that look like typos of each other
+    issues.extend(pair_typos(missing, unused));
+
+    // Check reads against deno.json --allow-env lists
+    issues.extend(find_permission_issues(&project.deno_permissions, usages));
+
+    // Check client-side reads for the framework's public prefix
+    issues.extend(find_client_prefix_issues(usages, project));
+
+    // Find secrets shipped to the browser through public prefixes
+    issues.extend(find_exposed_secrets(definitions, usages, project));
 
     // Find naming convention issues
     let rules = get_all_rules(config);
@@ -30,6 +53,27 @@ pub fn analyze(
         definitions,
         usages,
         &rules,
+        &get_all_synonyms(config),
+        config.naming.synonym_severity_level(),
+        &config.naming.ignore_patterns,
+    ));
+    issues.extend(find_case_collisions(
+        definitions,
+        usages,
+        &config.naming.ignore_patterns,
+    ));
+    if config.naming.service_prefixes {
+        issues.extend(find_prefix_outliers(
+            definitions,
+            usages,
+            &config.naming.ignore_patterns,
+        ));
+    }
+    issues.extend(find_convention_issues(
+        definitions,
+        usages,
+        &project.root,
+        &config.naming.conventions,
         &config.naming.ignore_patterns,
     ));
 
@@ -1,14 +1,19 @@
 use regex::Regex;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::Path;
 
-use crate::rules::NamingRule;
-use crate::types::{EnvVarDefinition, EnvVarUsage, Issue, IssueKind, Location};
+use super::secrets::PUBLIC_PREFIXES;
+use crate::config::ConventionConfig;
+use crate::rules::{check_conventions, NamingRule};
+use crate::types::{EnvVarDefinition, EnvVarUsage, Issue, IssueKind, Location, Severity};
 
 /// Find environment variables with inconsistent naming
 pub fn find_naming_issues(
     definitions: &[EnvVarDefinition],
     usages: &[EnvVarUsage],
     rules: &[NamingRule],
+    synonyms: &[Vec<String>],
+    synonym_severity: Severity,
     ignore_patterns: &[String],
 ) -> Vec<Issue> {
     let mut issues = Vec::new();
@@ -26,49 +31,48 @@ pub fn find_naming_issues(
         .chain(usages.iter().map(|u| u.name.as_str()))
         .collect();
 
+    // Sorted so regex rules report in a stable order
+    let mut sorted_names: Vec<&str> = all_names.iter().copied().collect();
+    sorted_names.sort_unstable();
+
     // Check each rule
     for rule in rules {
-        // Find which alternatives from this rule are present
-        let present_alternatives: Vec<&str> = rule
-            .alternatives
-            .iter()
-            .filter(|alt| all_names.contains(alt.as_str()))
-            .map(|s| s.as_str())
-            .collect();
+        // Names present under this rule, with the name each should become
+        let present_alternatives: Vec<(&str, String)> = if rule.regex {
+            let patterns: Vec<Regex> = rule
+                .alternatives
+                .iter()
+                .filter_map(|alt| Regex::new(alt).ok())
+                .collect();
+            sorted_names
+                .iter()
+                .filter_map(|name| {
+                    let caps = patterns.iter().find_map(|re| re.captures(name))?;
+                    let mut preferred = String::new();
+                    caps.expand(&rule.preferred, &mut preferred);
+                    (preferred != *name).then_some((*name, preferred))
+                })
+                .collect()
+        } else {
+            rule.alternatives
+                .iter()
+                .filter(|alt| all_names.contains(alt.as_str()))
+                .map(|alt| (alt.as_str(), rule.preferred.clone()))
+                .collect()
+        };
 
         // If any alternatives are present, suggest using the preferred name
-        for alt_name in present_alternatives {
+        for (alt_name, preferred) in present_alternatives {
             // Skip if the name matches an ignore pattern
             if ignore_regexes.iter().any(|re| re.is_match(alt_name)) {
                 continue;
             }
 
-            // Collect locations from both definitions and usages
-            let mut locations: Vec<Location> = Vec::new();
-
-            for def in definitions.iter().filter(|d| d.name == alt_name) {
-                locations.push(Location {
-                    file: def.source_file.clone(),
-                    line: Some(def.line),
-                    column: None,
-                });
-            }
-
-            for usage in usages.iter().filter(|u| u.name == alt_name) {
-                locations.push(Location {
-                    file: usage.file_path.clone(),
-                    line: Some(usage.line),
-                    column: Some(usage.column),
-                });
-            }
-
-            // Deduplicate locations by file and line
-            locations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
-            locations.dedup_by(|a, b| a.file == b.file && a.line == b.line);
+            let locations = name_locations(alt_name, definitions, usages);
 
             let message = format!(
                 "'{}' could be renamed to '{}' for consistency",
-                alt_name, rule.preferred
+                alt_name, preferred
             );
 
             issues.push(Issue {
@@ -79,7 +83,7 @@ pub fn find_naming_issues(
                 locations,
                 suggestion: Some(format!(
                     "Consider using '{}' instead of '{}'{}",
-                    rule.preferred,
+                    preferred,
                     alt_name,
                     rule.description
                         .as_ref()
@@ -90,13 +94,402 @@ pub fn find_naming_issues(
         }
     }
 
+    // Families of names that differ only by synonyms, such as DB_HOST and
+    // DATABASE_HOST. A family with a member an explicit rule renames is
+    // skipped, so the two never suggest conflicting names.
+    let reported: HashSet<String> = issues.iter().map(|i| i.var_name.clone()).collect();
+    for (canonical, members) in synonym_families(&sorted_names, synonyms) {
+        if members.iter().any(|m| reported.contains(*m)) {
+            continue;
+        }
+        for name in members.iter().copied().filter(|n| *n != canonical) {
+            if ignore_regexes.iter().any(|re| re.is_match(name)) {
+                continue;
+            }
+            issues.push(Issue {
+                kind: IssueKind::InconsistentNaming,
+                severity: synonym_severity,
+                var_name: name.to_string(),
+                message: format!(
+                    "'{}' names the same thing as {} with a different abbreviation",
+                    name,
+                    members
+                        .iter()
+                        .filter(|m| **m != name)
+                        .map(|m| format!("'{}'", m))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                locations: name_locations(name, definitions, usages),
+                suggestion: Some(format!(
+                    "Consider using '{}' instead of '{}' (synonyms)",
+                    canonical, name
+                )),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Find vars whose service prefix drifts from the rest of their cluster,
+/// such as `PAYMENTS_STRIPE_WEBHOOK` next to `STRIPE_KEY`, `STRIPE_SECRET`
+/// and `STRIPE_WEBHOOK_SECRET`. Names are clustered by each word they share;
+/// when most of a cluster puts the same words in front of it, the others are
+/// outliers, unless renaming any var with their prefix would collide with an
+/// existing name. Names ending in the shared word, like `REDIS_DB`, are never
+/// outliers. Framework public prefixes such as `VITE_` are set aside first,
+/// so `VITE_STRIPE_KEY` clusters as `STRIPE_KEY` and keeps its prefix.
+pub fn find_prefix_outliers(
+    definitions: &[EnvVarDefinition],
+    usages: &[EnvVarUsage],
+    ignore_patterns: &[String],
+) -> Vec<Issue> {
+    let ignore_regexes: Vec<Regex> = ignore_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+
+    let names: Vec<&str> = definitions
+        .iter()
+        .map(|d| d.name.as_str())
+        .chain(usages.iter().map(|u| u.name.as_str()))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let upper_names: HashSet<String> = names.iter().map(|n| n.to_uppercase()).collect();
+
+    // Word -> (name, public prefix, prefix through the word, rest after it)
+    let mut clusters: BTreeMap<String, Vec<(&str, &str, String, String)>> = BTreeMap::new();
+    for name in &names {
+        let upper = name.to_uppercase();
+        let public = PUBLIC_PREFIXES
+            .iter()
+            .copied()
+            .find(|p| upper.starts_with(p) && upper.len() > p.len())
+            .unwrap_or_default();
+        let words: Vec<&str> = upper[public.len()..].split('_').collect();
+        let mut seen = HashSet::new();
+        for (i, word) in words.iter().enumerate() {
+            if word.len() < 2 || word.chars().all(|c| c.is_ascii_digit()) || !seen.insert(*word) {
+                continue;
+            }
+            clusters.entry(word.to_string()).or_default().push((
+                name,
+                public,
+                words[..=i].join("_"),
+                words[i + 1..].join("_"),
+            ));
+        }
+    }
+
+    // Largest clusters first, so each outlier is reported once
+    let mut clusters: Vec<_> = clusters.into_iter().collect();
+    clusters.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(&b.0)));
+
+    let mut reported: HashSet<&str> = HashSet::new();
+    let mut issues = Vec::new();
+    for (word, members) in clusters {
+        // STRIPE_KEY and NEXT_PUBLIC_STRIPE_KEY count once
+        let distinct_names: BTreeSet<(&str, &str)> = members
+            .iter()
+            .map(|(_, _, prefix, rest)| (prefix.as_str(), rest.as_str()))
+            .collect();
+        if distinct_names.len() < 3 {
+            continue;
+        }
+        let mut prefix_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for (prefix, _) in &distinct_names {
+            *prefix_counts.entry(prefix).or_default() += 1;
+        }
+        let (dominant, count) = prefix_counts
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+            .map(|(p, c)| (p.to_string(), *c))
+            .unwrap();
+        if count < 2 || count * 2 <= distinct_names.len() {
+            continue;
+        }
+
+        let rename = |public: &str, rest: &str| format!("{}{}_{}", public, dominant, rest);
+        // A prefix with a rename that collides marks a separate instance,
+        // like ANALYTICS_DB_HOST next to DB_HOST
+        let distinct: HashSet<&str> = members
+            .iter()
+            .filter(|(_, public, prefix, rest)| {
+                *prefix != dominant
+                    && !rest.is_empty()
+                    && upper_names.contains(&rename(public, rest))
+            })
+            .map(|(_, _, prefix, _)| prefix.as_str())
+            .collect();
+
+        for (name, public, prefix, rest) in &members {
+            // In REDIS_DB the word qualifies the name rather than prefixing
+            // it, and DB alone would name a different var
+            if rest.is_empty()
+                || *prefix == dominant
+                || distinct.contains(prefix.as_str())
+                || reported.contains(name)
+                || ignore_regexes.iter().any(|re| re.is_match(name))
+            {
+                continue;
+            }
+            let rename = rename(public, rest);
+
+            reported.insert(name);
+            issues.push(Issue {
+                kind: IssueKind::InconsistentNaming,
+                severity: Severity::Info,
+                var_name: name.to_string(),
+                message: format!(
+                    "'{}' uses the prefix {}_ while {} other {} vars use {}_",
+                    name, prefix, count, word, dominant
+                ),
+                locations: name_locations(name, definitions, usages),
+                suggestion: Some(format!(
+                    "Consider using '{}' instead of '{}' (service prefix)",
+                    rename, name
+                )),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Group names that are equal once synonyms are normalized, and pick the
+/// canonical spelling for each group: the synonym the project uses most,
+/// or the group's first word on a tie
+fn synonym_families<'a>(
+    names: &[&'a str],
+    synonyms: &[Vec<String>],
+) -> Vec<(String, Vec<&'a str>)> {
+    let group_of = |word: &str| synonyms.iter().position(|g| g.iter().any(|w| w == word));
+
+    // How often each synonym appears across all names
+    let mut word_counts: BTreeMap<String, usize> = BTreeMap::new();
+    for name in names {
+        for word in name.to_uppercase().split('_') {
+            if group_of(word).is_some() {
+                *word_counts.entry(word.to_string()).or_default() += 1;
+            }
+        }
+    }
+    let preferred: Vec<&str> = synonyms
+        .iter()
+        .map(|group| {
+            let mut best = group[0].as_str();
+            for word in group {
+                let count = |w: &str| word_counts.get(w).copied().unwrap_or(0);
+                if count(word) > count(best) {
+                    best = word;
+                }
+            }
+            best
+        })
+        .collect();
+
+    // Normalized name -> spellings
+    let mut families: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+    for name in names {
+        let upper = name.to_uppercase();
+        let normalized: Vec<&str> = upper
+            .split('_')
+            .map(|word| group_of(word).map_or(word, |g| preferred[g]))
+            .collect();
+        families.entry(normalized.join("_")).or_default().push(name);
+    }
+
+    families
+        .into_iter()
+        .filter(|(_, members)| {
+            // Spellings that differ only by case are case collisions
+            members.iter().any(|m| !m.eq_ignore_ascii_case(members[0]))
+        })
+        .map(|(canonical, members)| {
+            // Keep the project's own casing when a member already matches
+            let canonical = members
+                .iter()
+                .find(|m| m.eq_ignore_ascii_case(&canonical))
+                .map_or(canonical, |m| m.to_string());
+            (canonical, members)
+        })
+        .collect()
+}
+
+/// Where a name is defined or used, deduplicated by file and line
+fn name_locations(
+    name: &str,
+    definitions: &[EnvVarDefinition],
+    usages: &[EnvVarUsage],
+) -> Vec<Location> {
+    let mut locations: Vec<Location> = definitions
+        .iter()
+        .filter(|d| d.name == name)
+        .map(|d| Location {
+            file: d.source_file.clone(),
+            line: Some(d.line),
+            column: None,
+        })
+        .chain(usages.iter().filter(|u| u.name == name).map(|u| Location {
+            file: u.file_path.clone(),
+            line: Some(u.line),
+            column: Some(u.column),
+        }))
+        .collect();
+    locations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+    locations.dedup_by(|a, b| a.file == b.file && a.line == b.line);
+    locations
+}
+
+/// Find var names that differ only by case, such as `Api_Key` and `API_KEY`.
+/// Windows treats env var names case-insensitively, so these collide there.
+pub fn find_case_collisions(
+    definitions: &[EnvVarDefinition],
+    usages: &[EnvVarUsage],
+    ignore_patterns: &[String],
+) -> Vec<Issue> {
+    let ignore_regexes: Vec<Regex> = ignore_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+
+    // Uppercased name -> distinct spellings
+    let mut groups: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for name in definitions
+        .iter()
+        .map(|d| d.name.as_str())
+        .chain(usages.iter().map(|u| u.name.as_str()))
+    {
+        let spellings = groups.entry(name.to_uppercase()).or_default();
+        if !spellings.contains(&name) {
+            spellings.push(name);
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (upper, spellings) in groups {
+        if spellings.len() < 2 {
+            continue;
+        }
+
+        // Prefer the conventional uppercase spelling, else the most common one
+        let count = |name: &str| {
+            definitions.iter().filter(|d| d.name == name).count()
+                + usages.iter().filter(|u| u.name == name).count()
+        };
+        let preferred = if spellings.contains(&upper.as_str()) {
+            upper.as_str()
+        } else {
+            spellings
+                .iter()
+                .copied()
+                .max_by_key(|name| count(name))
+                .unwrap_or(spellings[0])
+        };
+
+        for name in spellings.iter().copied().filter(|n| *n != preferred) {
+            if ignore_regexes.iter().any(|re| re.is_match(name)) {
+                continue;
+            }
+
+            issues.push(Issue {
+                kind: IssueKind::InconsistentNaming,
+                severity: Severity::Warning,
+                var_name: name.to_string(),
+                message: format!(
+                    "'{}' differs from '{}' only by case, which collides on Windows",
+                    name, preferred
+                ),
+                locations: name_locations(name, definitions, usages),
+                suggestion: Some(format!(
+                    "Consider using '{}' instead of '{}' (case-only collision)",
+                    preferred, name
+                )),
+            });
+        }
+    }
+
+    issues
+}
+
+/// Find names that break the `[naming.conventions]` style rules, with a
+/// rewrite that follows them. Prefix rule paths are relative to `root`.
+pub fn find_convention_issues(
+    definitions: &[EnvVarDefinition],
+    usages: &[EnvVarUsage],
+    root: &Path,
+    conventions: &ConventionConfig,
+    ignore_patterns: &[String],
+) -> Vec<Issue> {
+    let ignore_regexes: Vec<Regex> = ignore_patterns
+        .iter()
+        .filter_map(|p| Regex::new(p).ok())
+        .collect();
+
+    // Name -> locations, in name order
+    let mut names: BTreeMap<&str, Vec<Location>> = BTreeMap::new();
+    for def in definitions {
+        names.entry(&def.name).or_default().push(Location {
+            file: def.source_file.clone(),
+            line: Some(def.line),
+            column: None,
+        });
+    }
+    for usage in usages {
+        names.entry(&usage.name).or_default().push(Location {
+            file: usage.file_path.clone(),
+            line: Some(usage.line),
+            column: Some(usage.column),
+        });
+    }
+
+    let mut issues = Vec::new();
+    for (name, mut locations) in names {
+        if ignore_regexes.iter().any(|re| re.is_match(name)) {
+            continue;
+        }
+        let files: Vec<&Path> = locations
+            .iter()
+            .map(|l| l.file.strip_prefix(root).unwrap_or(&l.file))
+            .collect();
+        let Some(violation) = check_conventions(name, &files, conventions) else {
+            continue;
+        };
+
+        locations.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+        locations.dedup_by(|a, b| a.file == b.file && a.line == b.line);
+
+        let suggestion = if violation.rewrite == name {
+            format!("Rename '{}' to follow the naming conventions", name)
+        } else {
+            format!(
+                "Consider using '{}' instead of '{}' (naming conventions)",
+                violation.rewrite, name
+            )
+        };
+        issues.push(Issue {
+            kind: IssueKind::InconsistentNaming,
+            severity: conventions.severity_level(),
+            var_name: name.to_string(),
+            message: format!(
+                "'{}' breaks naming conventions: {}",
+                name,
+                violation.problems.join("; ")
+            ),
+            locations,
+            suggestion: Some(suggestion),
+        });
+    }
+
     issues
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{Language, Severity};
+    use crate::types::Language;
     use std::path::PathBuf;
 
     fn make_definition(name: &str) -> EnvVarDefinition {
@@ -105,6 +498,7 @@ mod tests {
             value: Some("test".to_string()),
             source_file: PathBuf::from(".env"),
             line: 1,
+            environment: None,
         }
     }
 
@@ -116,6 +510,9 @@ mod tests {
             column: 5,
             language: Language::JavaScript,
             context: None,
+            default_value: None,
+            required: None,
+            exposure: None,
         }
     }
 
@@ -126,6 +523,7 @@ mod tests {
             alternatives: alternatives.into_iter().map(String::from).collect(),
             preferred: preferred.to_string(),
             severity: Severity::Warning,
+            regex: false,
         }
     }
 
@@ -135,7 +533,7 @@ mod tests {
         let usages = vec![make_usage("DATABASE_URL")];
         let rules = vec![make_rule(vec!["DB_URL", "DB_HOST"], "DATABASE_URL")];
 
-        let issues = find_naming_issues(&definitions, &usages, &rules, &[]);
+        let issues = find_naming_issues(&definitions, &usages, &rules, &[], Severity::Warning, &[]);
         assert!(issues.is_empty());
     }
 
@@ -145,7 +543,7 @@ mod tests {
         let usages = vec![make_usage("DB_URL")];
         let rules = vec![make_rule(vec!["DB_URL", "DB_HOST"], "DATABASE_URL")];
 
-        let issues = find_naming_issues(&definitions, &usages, &rules, &[]);
+        let issues = find_naming_issues(&definitions, &usages, &rules, &[], Severity::Warning, &[]);
         assert_eq!(issues.len(), 1);
         assert_eq!(issues[0].var_name, "DB_URL");
         assert_eq!(issues[0].kind, IssueKind::InconsistentNaming);
@@ -158,7 +556,235 @@ mod tests {
         let rules = vec![make_rule(vec!["_INTERNAL_VAR"], "INTERNAL_VAR")];
         let ignore = vec!["^_".to_string()];
 
-        let issues = find_naming_issues(&definitions, &usages, &rules, &ignore);
+        let issues = find_naming_issues(
+            &definitions,
+            &usages,
+            &rules,
+            &[],
+            Severity::Warning,
+            &ignore,
+        );
+        assert!(issues.is_empty());
+    }
+
+    #[test]
+    fn test_case_collision() {
+        let definitions = vec![make_definition("API_KEY")];
+        let usages = vec![make_usage("Api_Key"), make_usage("API_KEY")];
+
+        let issues = find_case_collisions(&definitions, &usages, &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "Api_Key");
+        assert_eq!(issues[0].kind, IssueKind::InconsistentNaming);
+    }
+
+    #[test]
+    fn test_no_case_collision() {
+        let definitions = vec![make_definition("http_proxy")];
+        let usages = vec![make_usage("http_proxy"), make_usage("HTTPS_PROXY")];
+
+        let issues = find_case_collisions(&definitions, &usages, &[]);
         assert!(issues.is_empty());
     }
+
+    #[test]
+    fn test_convention_issues() {
+        let definitions = vec![make_definition("apiUrl"), make_definition("PORT")];
+        let usages = vec![make_usage("apiUrl"), make_usage("_debugFlag")];
+        let conventions = ConventionConfig {
+            screaming_snake: true,
+            ..Default::default()
+        };
+
+        let issues = find_convention_issues(
+            &definitions,
+            &usages,
+            Path::new("."),
+            &conventions,
+            &["^_".to_string()],
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "apiUrl");
+        assert_eq!(issues[0].kind, IssueKind::InconsistentNaming);
+        assert_eq!(issues[0].locations.len(), 2);
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Consider using 'API_URL' instead of 'apiUrl' (naming conventions)")
+        );
+    }
+
+    #[test]
+    fn test_regex_rule_with_captures() {
+        let definitions = vec![
+            make_definition("BILLING_DB_URL"),
+            make_definition("SEARCH_DB_HOST"),
+            make_definition("SEARCH_DATABASE_URL"),
+        ];
+        let mut rule = make_rule(
+            vec![r"^(?P<svc>\w+?)_DB_(?:URL|HOST)$"],
+            "${svc}_DATABASE_URL",
+        );
+        rule.regex = true;
+
+        let issues = find_naming_issues(&definitions, &[], &[rule], &[], Severity::Warning, &[]);
+        let renames: Vec<_> = issues
+            .iter()
+            .map(|i| (i.var_name.as_str(), i.suggestion.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            renames,
+            vec![
+                (
+                    "BILLING_DB_URL",
+                    "Consider using 'BILLING_DATABASE_URL' instead of 'BILLING_DB_URL' (Test rule)"
+                ),
+                (
+                    "SEARCH_DB_HOST",
+                    "Consider using 'SEARCH_DATABASE_URL' instead of 'SEARCH_DB_HOST' (Test rule)"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_synonym_families() {
+        let definitions = vec![
+            make_definition("DB_HOST"),
+            make_definition("DATABASE_PASSWORD"),
+            make_definition("REDIS_PWD"),
+            make_definition("REDIS_PASSWORD"),
+        ];
+        let usages = vec![make_usage("DATABASE_HOST"), make_usage("DB_NAME")];
+        let synonyms = crate::rules::get_builtin_synonyms();
+
+        let issues = find_naming_issues(
+            &definitions,
+            &usages,
+            &[],
+            &synonyms,
+            Severity::Warning,
+            &[],
+        );
+        let renames: Vec<_> = issues
+            .iter()
+            .map(|i| (i.var_name.as_str(), i.suggestion.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            renames,
+            vec![
+                (
+                    "DB_HOST",
+                    "Consider using 'DATABASE_HOST' instead of 'DB_HOST' (synonyms)"
+                ),
+                (
+                    "REDIS_PWD",
+                    "Consider using 'REDIS_PASSWORD' instead of 'REDIS_PWD' (synonyms)"
+                ),
+            ]
+        );
+        assert_eq!(issues[0].kind, IssueKind::InconsistentNaming);
+    }
+
+    #[test]
+    fn test_synonym_majority_wins() {
+        let definitions = vec![
+            make_definition("DB_HOST"),
+            make_definition("DB_PORT"),
+            make_definition("DATABASE_HOST"),
+        ];
+        let synonyms = crate::rules::get_builtin_synonyms();
+        let issues = find_naming_issues(&definitions, &[], &[], &synonyms, Severity::Info, &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "DATABASE_HOST");
+        assert_eq!(issues[0].severity, Severity::Info);
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Consider using 'DB_HOST' instead of 'DATABASE_HOST' (synonyms)")
+        );
+    }
+
+    #[test]
+    fn test_synonyms_defer_to_rules() {
+        let definitions = vec![
+            make_definition("DB_HOST"),
+            make_definition("DB_PORT"),
+            make_definition("DATABASE_HOST"),
+        ];
+        let rules = crate::rules::get_builtin_rules();
+        let synonyms = crate::rules::get_builtin_synonyms();
+        let issues =
+            find_naming_issues(&definitions, &[], &rules, &synonyms, Severity::Warning, &[]);
+        // The rule renames DB_HOST to DATABASE_URL; the synonym family would
+        // have renamed DATABASE_HOST to DB_HOST
+        let names: Vec<_> = issues.iter().map(|i| i.var_name.as_str()).collect();
+        assert_eq!(names, vec!["DB_HOST"]);
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Consider using 'DATABASE_URL' instead of 'DB_HOST' (Database connection URL)")
+        );
+    }
+
+    #[test]
+    fn test_prefix_outliers() {
+        let definitions = vec![
+            make_definition("STRIPE_KEY"),
+            make_definition("STRIPE_SECRET"),
+            make_definition("STRIPE_WEBHOOK_SECRET"),
+            make_definition("DATABASE_URL"),
+            make_definition("REDIS_URL"),
+            make_definition("API_URL"),
+        ];
+        let usages = vec![make_usage("PAYMENTS_STRIPE_WEBHOOK")];
+
+        let issues = find_prefix_outliers(&definitions, &usages, &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "PAYMENTS_STRIPE_WEBHOOK");
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Consider using 'STRIPE_WEBHOOK' instead of 'PAYMENTS_STRIPE_WEBHOOK' (service prefix)")
+        );
+        assert_eq!(issues[0].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_prefix_outliers_ignore_public_prefixes() {
+        let definitions = vec![
+            make_definition("STRIPE_KEY"),
+            make_definition("STRIPE_SECRET"),
+            make_definition("VITE_STRIPE_PUBLISHABLE_KEY"),
+            make_definition("NEXT_PUBLIC_STRIPE_KEY"),
+            make_definition("VITE_PAYMENTS_STRIPE_ACCOUNT"),
+        ];
+
+        let issues = find_prefix_outliers(&definitions, &[], &[]);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].var_name, "VITE_PAYMENTS_STRIPE_ACCOUNT");
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Consider using 'VITE_STRIPE_ACCOUNT' instead of 'VITE_PAYMENTS_STRIPE_ACCOUNT' (service prefix)")
+        );
+    }
+
+    #[test]
+    fn test_prefix_outlier_rename_collision() {
+        let definitions = vec![
+            make_definition("DB_HOST"),
+            make_definition("DB_PORT"),
+            make_definition("DB_NAME"),
+            make_definition("ANALYTICS_DB_HOST"),
+            make_definition("ANALYTICS_DB_USER"),
+        ];
+        assert!(find_prefix_outliers(&definitions, &[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_prefix_outlier_trailing_word() {
+        let definitions = vec![
+            make_definition("DB_HOST"),
+            make_definition("DB_PORT"),
+            make_definition("DB_NAME"),
+            make_definition("REDIS_DB"),
+        ];
+        assert!(find_prefix_outliers(&definitions, &[], &[]).is_empty());
+    }
 }
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::types::{DenoPermissions, EnvVarUsage, Issue, IssueKind, Language, Location, Severity};
+
+/// Check env reads in Deno projects against the `--allow-env` lists in their
+/// deno.json. Reads that are not granted fail at runtime; grants that are
+/// never read widen the permission surface for nothing.
+pub fn find_permission_issues(
+    permissions: &[DenoPermissions],
+    usages: &[EnvVarUsage],
+) -> Vec<Issue> {
+    // Usages of each project, by the nearest deno.json above the file
+    let mut scoped: Vec<Vec<&EnvVarUsage>> = vec![Vec::new(); permissions.len()];
+    for usage in usages {
+        if !matches!(usage.language, Language::JavaScript | Language::TypeScript) {
+            continue;
+        }
+        let nearest = permissions
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| {
+                let root = p.config_file.parent().unwrap_or(Path::new(""));
+                usage
+                    .file_path
+                    .starts_with(root)
+                    .then_some((i, root.components().count()))
+            })
+            .max_by_key(|(_, depth)| *depth);
+        if let Some((i, _)) = nearest {
+            scoped[i].push(usage);
+        }
+    }
+
+    let mut issues = Vec::new();
+    for (permissions, usages) in permissions.iter().zip(scoped) {
+        if permissions.unrestricted {
+            continue;
+        }
+        let config = permissions.config_file.display();
+
+        // Reads that the runtime will deny
+        let mut denied: BTreeMap<&str, Vec<Location>> = BTreeMap::new();
+        for usage in &usages {
+            if !is_granted(permissions, &usage.name) {
+                denied.entry(&usage.name).or_default().push(Location {
+                    file: usage.file_path.clone(),
+                    line: Some(usage.line),
+                    column: Some(usage.column),
+                });
+            }
+        }
+        for (name, locations) in denied {
+            issues.push(Issue {
+                kind: IssueKind::EnvPermission,
+                severity: Severity::Error,
+                var_name: name.to_string(),
+                message: format!(
+                    "'{}' is read but not granted by --allow-env in {}",
+                    name, config
+                ),
+                locations,
+                suggestion: Some(format!("Add {} to --allow-env in {}", name, config)),
+            });
+        }
+
+        // Grants nothing reads
+        for grant in &permissions.granted {
+            if grant.name.ends_with('*') || usages.iter().any(|u| u.name == grant.name) {
+                continue;
+            }
+            issues.push(Issue {
+                kind: IssueKind::EnvPermission,
+                severity: Severity::Warning,
+                var_name: grant.name.clone(),
+                message: format!(
+                    "'{}' is granted by --allow-env in {} but never read",
+                    grant.name, config
+                ),
+                locations: vec![Location {
+                    file: grant.source_file.clone(),
+                    line: Some(grant.line),
+                    column: None,
+                }],
+                suggestion: Some(format!("Remove {} from --allow-env", grant.name)),
+            });
+        }
+    }
+
+    issues
+}
+
+fn is_granted(permissions: &DenoPermissions, name: &str) -> bool {
+    permissions
+        .granted
+        .iter()
+        .any(|g| match g.name.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => g.name == name,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::EnvVarDefinition;
+    use std::path::PathBuf;
+
+    fn make_permissions(config: &str, granted: &[&str]) -> DenoPermissions {
+        DenoPermissions {
+            config_file: PathBuf::from(config),
+            unrestricted: false,
+            granted: granted
+                .iter()
+                .map(|name| EnvVarDefinition {
+                    name: name.to_string(),
+                    value: None,
+                    source_file: PathBuf::from(config),
+                    line: 3,
+                    environment: None,
+                })
+                .collect(),
+        }
+    }
+
+    fn make_usage(name: &str, path: &str) -> EnvVarUsage {
+        EnvVarUsage {
+            name: name.to_string(),
+            file_path: PathBuf::from(path),
+            line: 10,
+            column: 5,
+            language: Language::TypeScript,
+            context: None,
+            default_value: None,
+            required: None,
+            exposure: None,
+        }
+    }
+
+    #[test]
+    fn test_denied_and_unused_grants() {
+        let permissions = vec![make_permissions(
+            "edge/deno.json",
+            &["PORT", "STALE", "AWS_*"],
+        )];
+        let usages = vec![
+            make_usage("PORT", "edge/main.ts"),
+            make_usage("AWS_REGION", "edge/main.ts"),
+            make_usage("DATABASE_URL", "edge/db.ts"),
+            // Outside the Deno project
+            make_usage("NODE_ONLY", "web/app.ts"),
+        ];
+
+        let issues = find_permission_issues(&permissions, &usages);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].var_name, "DATABASE_URL");
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[1].var_name, "STALE");
+        assert_eq!(issues[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_nearest_config_applies() {
+        let permissions = vec![
+            make_permissions("deno.json", &["ROOT_VAR"]),
+            make_permissions("functions/deno.json", &["FN_VAR"]),
+        ];
+        let usages = vec![
+            make_usage("ROOT_VAR", "main.ts"),
+            make_usage("FN_VAR", "functions/hello.ts"),
+        ];
+
+        assert!(find_permission_issues(&permissions, &usages).is_empty());
+    }
+
+    #[test]
+    fn test_unrestricted_is_not_checked() {
+        let mut permissions = make_permissions("deno.json", &[]);
+        permissions.unrestricted = true;
+        let usages = vec![make_usage("ANYTHING", "main.ts")];
+
+        assert!(find_permission_issues(&[permissions], &usages).is_empty());
+    }
+}
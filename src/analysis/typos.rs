@@ -0,0 +1,218 @@
+use crate::types::{Issue, IssueKind, Severity};
+
+/// Pair missing vars with unused definitions that look like the same name
+/// misspelled, such as `DATABSE_URL` read in code and `DATABASE_URL` defined
+/// in .env. Each pair is replaced by a single typo issue; everything else is
+/// returned unchanged.
+pub fn pair_typos(missing: Vec<Issue>, unused: Vec<Issue>) -> Vec<Issue> {
+    // Candidate pairs, closest first
+    let mut candidates: Vec<(usize, usize, usize)> = Vec::new();
+    for (m, used) in missing.iter().enumerate() {
+        for (u, defined) in unused.iter().enumerate() {
+            if let Some(distance) = typo_distance(&used.var_name, &defined.var_name) {
+                candidates.push((distance, m, u));
+            }
+        }
+    }
+    candidates.sort();
+
+    let mut missing: Vec<Option<Issue>> = missing.into_iter().map(Some).collect();
+    let mut unused: Vec<Option<Issue>> = unused.into_iter().map(Some).collect();
+    let mut issues = Vec::new();
+    for (_, m, u) in candidates {
+        if missing[m].is_none() || unused[u].is_none() {
+            continue;
+        }
+        let used = missing[m].take().unwrap();
+        let defined = unused[u].take().unwrap();
+
+        let mut locations = used.locations;
+        locations.extend(defined.locations);
+        issues.push(Issue {
+            kind: IssueKind::PossibleTypo,
+            // A misspelt optional var silently falls back to its default
+            severity: used.severity.max(Severity::Warning),
+            message: format!(
+                "'{}' is used in code but not defined, and '{}' is defined but never used",
+                used.var_name, defined.var_name
+            ),
+            locations,
+            suggestion: Some(format!(
+                "Did you mean '{}'? Rename one so code and .env agree",
+                defined.var_name
+            )),
+            var_name: used.var_name,
+        });
+    }
+
+    issues.extend(missing.into_iter().flatten());
+    issues.extend(unused.into_iter().flatten());
+    issues
+}
+
+/// How far apart two names are, if close enough to be a typo. Names that
+/// differ only by case or underscores, or list the same words in another
+/// order, count as distance 0. Otherwise the names must differ in a single
+/// word of at least 4 characters, by 1 edit, or 2 for words of 8 or more.
+/// Different numbers (`DB1`/`DB2`) and swapped words (`APP`/`API`,
+/// `HOST`/`PORT`) name different vars, not typos.
+fn typo_distance(a: &str, b: &str) -> Option<usize> {
+    let squash = |s: &str| s.replace('_', "").to_uppercase();
+    if squash(a) == squash(b) {
+        return Some(0);
+    }
+    let words = |s: &str| -> Vec<String> {
+        s.to_uppercase()
+            .split('_')
+            .filter(|w| !w.is_empty())
+            .map(String::from)
+            .collect()
+    };
+    let (words_a, words_b) = (words(a), words(b));
+    let (mut sorted_a, mut sorted_b) = (words_a.clone(), words_b.clone());
+    sorted_a.sort();
+    sorted_b.sort();
+    if sorted_a == sorted_b {
+        return Some(0);
+    }
+
+    // Exactly one word may differ
+    if words_a.len() != words_b.len() {
+        return None;
+    }
+    let mut differing = words_a.iter().zip(&words_b).filter(|(x, y)| x != y);
+    let (word_a, word_b) = differing.next()?;
+    if differing.next().is_some() {
+        return None;
+    }
+
+    let without_digits = |w: &str| w.replace(|c: char| c.is_ascii_digit(), "");
+    if without_digits(word_a) == without_digits(word_b) {
+        return None;
+    }
+    let limit = match word_a.len().min(word_b.len()) {
+        0..=3 => return None,
+        4..=7 => 1,
+        _ => 2,
+    };
+    let distance = edit_distance(word_a, word_b);
+    (distance <= limit).then_some(distance)
+}
+
+/// Levenshtein distance with adjacent transpositions counted as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    rows[0] = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Location;
+    use std::path::PathBuf;
+
+    fn issue(kind: IssueKind, name: &str, file: &str) -> Issue {
+        Issue {
+            kind,
+            severity: Severity::Error,
+            var_name: name.to_string(),
+            message: String::new(),
+            locations: vec![Location {
+                file: PathBuf::from(file),
+                line: Some(1),
+                column: None,
+            }],
+            suggestion: None,
+        }
+    }
+
+    #[test]
+    fn test_pairs_typos() {
+        let missing = vec![
+            issue(IssueKind::MissingEnvVar, "DATABSE_URL", "src/db.js"),
+            issue(IssueKind::MissingEnvVar, "KEY_API", "src/api.js"),
+            issue(IssueKind::MissingEnvVar, "SENTRY_DSN", "src/app.js"),
+        ];
+        let unused = vec![
+            issue(IssueKind::UnusedEnvVar, "DATABASE_URL", ".env"),
+            issue(IssueKind::UnusedEnvVar, "API_KEY", ".env"),
+            issue(IssueKind::UnusedEnvVar, "REDIS_URL", ".env"),
+        ];
+
+        let issues = pair_typos(missing, unused);
+        let kinds: Vec<_> = issues
+            .iter()
+            .map(|i| (i.kind, i.var_name.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (IssueKind::PossibleTypo, "KEY_API"),
+                (IssueKind::PossibleTypo, "DATABSE_URL"),
+                (IssueKind::MissingEnvVar, "SENTRY_DSN"),
+                (IssueKind::UnusedEnvVar, "REDIS_URL"),
+            ]
+        );
+        assert_eq!(
+            issues[1].suggestion.as_deref(),
+            Some("Did you mean 'DATABASE_URL'? Rename one so code and .env agree")
+        );
+        assert_eq!(issues[1].locations.len(), 2);
+    }
+
+    #[test]
+    fn test_closest_definition_wins() {
+        let missing = vec![issue(IssueKind::MissingEnvVar, "NOTIFICATON_URL", "a.js")];
+        let unused = vec![
+            issue(IssueKind::UnusedEnvVar, "NOTIFICATIONS_URL", ".env"),
+            issue(IssueKind::UnusedEnvVar, "NOTIFICATION_URL", ".env"),
+        ];
+        let issues = pair_typos(missing, unused);
+        assert_eq!(
+            issues[0].suggestion.as_deref(),
+            Some("Did you mean 'NOTIFICATION_URL'? Rename one so code and .env agree")
+        );
+        assert_eq!(issues[1].var_name, "NOTIFICATIONS_URL");
+    }
+
+    #[test]
+    fn test_typo_distance() {
+        assert_eq!(typo_distance("DATABSE_URL", "DATABASE_URL"), Some(1));
+        assert_eq!(typo_distance("APIKEY", "API_KEY"), Some(0));
+        assert_eq!(typo_distance("STRIPE_KEY", "KEY_STRIPE"), Some(0));
+        assert_eq!(typo_distance("PORT", "PROT"), Some(1));
+        assert_eq!(typo_distance("DB", "DC"), None);
+        assert_eq!(typo_distance("AWS_REGION", "GCP_REGION"), None);
+    }
+
+    #[test]
+    fn test_different_vars_are_not_typos() {
+        assert_eq!(typo_distance("REDIS_HOST", "REDIS_PORT"), None);
+        assert_eq!(typo_distance("APP_PORT", "API_PORT"), None);
+        assert_eq!(typo_distance("DB1_URL", "DB2_URL"), None);
+        assert_eq!(typo_distance("S3_BUCKET_1", "S3_BUCKET_2"), None);
+        assert_eq!(typo_distance("CACHE_URL", "CACHE_URL_FALLBACK"), None);
+        // One misspelt word, but two words differ
+        assert_eq!(typo_distance("DATABSE_HOTS", "DATABASE_HOST"), None);
+    }
+}
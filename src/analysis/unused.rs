@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{BTreeSet, HashSet};
 
 use crate::types::{EnvVarDefinition, EnvVarUsage, Issue, IssueKind, Location, Severity};
 
@@ -27,7 +27,17 @@ pub fn find_unused_vars(definitions: &[EnvVarDefinition], usages: &[EnvVarUsage]
             })
             .collect();
 
-        let message = format!("'{}' is defined but never used in code", name);
+        // Name the deployment environments the definitions are scoped to
+        let environments: BTreeSet<&str> = definitions
+            .iter()
+            .filter(|d| d.name == name)
+            .filter_map(|d| d.environment.as_deref())
+            .collect();
+        let mut message = format!("'{}' is defined but never used in code", name);
+        if !environments.is_empty() {
+            let environments: Vec<&str> = environments.into_iter().collect();
+            message.push_str(&format!(" (env: {})", environments.join(", ")));
+        }
 
         issues.push(Issue {
             kind: IssueKind::UnusedEnvVar,
@@ -57,6 +67,7 @@ mod tests {
             value: Some("test".to_string()),
             source_file: PathBuf::from(".env"),
             line: 1,
+            environment: None,
         }
     }
 
@@ -68,6 +79,9 @@ mod tests {
             column: 5,
             language: Language::JavaScript,
             context: None,
+            default_value: None,
+            required: None,
+            exposure: None,
         }
     }
 
@@ -100,4 +114,20 @@ mod tests {
         let issues = find_unused_vars(&definitions, &usages);
         assert_eq!(issues.len(), 2);
     }
+
+    #[test]
+    fn test_unused_names_environments() {
+        let mut production = make_definition("API_HOST");
+        production.environment = Some("production".to_string());
+        let mut staging = make_definition("API_HOST");
+        staging.environment = Some("staging".to_string());
+        let definitions = vec![make_definition("API_HOST"), staging, production];
+
+        let issues = find_unused_vars(&definitions, &[]);
+        assert_eq!(
+            issues[0].message,
+            "'API_HOST' is defined but never used in code (env: production, staging)"
+        );
+        assert_eq!(issues[0].locations.len(), 3);
+    }
 }
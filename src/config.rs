@@ -2,7 +2,16 @@ use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::path::Path;
 
-use crate::types::Severity;
+use crate::types::{ClientFramework, Language, Severity};
+
+/// Names accepted in `template_presets`
+pub const TEMPLATE_PRESETS: &[&str] = &["vite", "cra", "jinja", "ansible"];
+
+/// Names accepted in `[client] frameworks`
+pub const CLIENT_FRAMEWORKS: &[&str] = &["vite", "cra", "next"];
+
+/// Values accepted as `kind` in `[[scan.patterns]]`
+pub const PATTERN_KINDS: &[&str] = &["read", "write"];
 
 /// Main configuration structure
 #[derive(Debug, Deserialize, Default)]
@@ -15,6 +24,9 @@ pub struct Config {
 
     #[serde(default)]
     pub output: OutputConfig,
+
+    #[serde(default)]
+    pub client: ClientConfig,
 }
 
 /// Configuration for file scanning
@@ -35,6 +47,42 @@ pub struct ScanConfig {
     /// Languages to scan (None = all supported languages)
     #[serde(default)]
     pub languages: Option<Vec<String>>,
+
+    /// Treat Java `System.getProperty` lookups as env var usages
+    #[serde(default)]
+    pub java_system_properties: bool,
+
+    /// Cloudflare Workers mode: read wrangler.toml and .dev.vars, and `env.X` in Worker entrypoints
+    #[serde(default)]
+    pub workers: bool,
+
+    /// Scanning engine: "regex" (default) or "ast", which needs the `ast` cargo feature
+    #[serde(default = "default_parser")]
+    pub parser: String,
+
+    /// Regex that detected var names must match (e.g. "^[A-Z_][A-Z0-9_]*$" for uppercase only)
+    #[serde(default = "default_name_pattern")]
+    pub name_pattern: String,
+
+    /// User-defined env access patterns
+    #[serde(default)]
+    pub patterns: Vec<CustomPattern>,
+
+    /// Built-in template placeholder presets: "vite", "cra", "jinja", "ansible"
+    #[serde(default)]
+    pub template_presets: Vec<String>,
+
+    /// User-defined template placeholder delimiters
+    #[serde(default)]
+    pub templates: Vec<TemplatePlaceholder>,
+
+    /// Project functions that take an env var name, in addition to those detected
+    #[serde(default)]
+    pub wrappers: Vec<WrapperConfig>,
+
+    /// Files whose string constants name env vars, e.g. "src/config/env-keys.ts"
+    #[serde(default)]
+    pub key_modules: Vec<String>,
 }
 
 impl Default for ScanConfig {
@@ -44,10 +92,97 @@ impl Default for ScanConfig {
             include: default_include(),
             exclude: default_exclude(),
             languages: None,
+            java_system_properties: false,
+            workers: false,
+            parser: default_parser(),
+            name_pattern: default_name_pattern(),
+            patterns: Vec::new(),
+            template_presets: Vec::new(),
+            templates: Vec::new(),
+            wrappers: Vec::new(),
+            key_modules: Vec::new(),
         }
     }
 }
 
+fn default_parser() -> String {
+    "regex".to_string()
+}
+
+fn default_name_pattern() -> String {
+    "^[A-Za-z_][A-Za-z0-9_]*$".to_string()
+}
+
+/// A user-defined regex for project-specific env helpers
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomPattern {
+    /// Languages whose files this pattern applies to
+    pub languages: Vec<String>,
+
+    /// Regex with a `name` capture group (or a first group) holding the var name.
+    /// An optional `default` group captures a fallback value.
+    pub regex: String,
+
+    /// Access kind: "read" or "write"
+    #[serde(default = "default_pattern_kind")]
+    pub kind: String,
+
+    /// Extra file extensions to scan with this pattern
+    #[serde(default)]
+    pub extensions: Vec<String>,
+}
+
+/// A user-defined placeholder syntax such as `${NAME}` in template files
+#[derive(Debug, Clone, Deserialize)]
+pub struct TemplatePlaceholder {
+    /// Opening delimiter, e.g. "${"
+    pub open: String,
+
+    /// Closing delimiter, e.g. "}"
+    pub close: String,
+
+    /// File extensions holding these placeholders
+    pub extensions: Vec<String>,
+
+    /// Only names starting with this prefix are env vars
+    #[serde(default)]
+    pub prefix: String,
+}
+
+/// A project helper such as `requireEnv("DB_URL")` declared in `[[scan.wrappers]]`
+#[derive(Debug, Clone, Deserialize)]
+pub struct WrapperConfig {
+    /// Function or method name
+    pub name: String,
+
+    /// Languages whose files call it (empty = all)
+    #[serde(default)]
+    pub languages: Vec<String>,
+
+    /// Index of the argument holding the var name
+    #[serde(default)]
+    pub arg: usize,
+
+    /// Index of the argument holding a fallback value
+    #[serde(default)]
+    pub default_arg: Option<usize>,
+
+    /// Whether the helper fails when the var is unset
+    #[serde(default)]
+    pub required: Option<bool>,
+}
+
+fn default_pattern_kind() -> String {
+    "read".to_string()
+}
+
+impl CustomPattern {
+    /// Writes provide the var to the process rather than requiring it
+    pub fn is_write(&self) -> bool {
+        self.kind.eq_ignore_ascii_case("write")
+    }
+}
+
 fn default_env_files() -> Vec<String> {
     vec![
         ".env".to_string(),
@@ -88,6 +223,27 @@ pub struct NamingConfig {
     /// Patterns to ignore (regex)
     #[serde(default)]
     pub ignore_patterns: Vec<String>,
+
+    /// Style conventions every name must follow
+    #[serde(default)]
+    pub conventions: ConventionConfig,
+
+    /// Use the built-in synonym groups (DB/DATABASE, PWD/PASSWORD, ...)
+    #[serde(default = "default_true")]
+    pub builtin_synonyms: bool,
+
+    /// Extra synonym groups. The spelling the project uses most is
+    /// suggested, with the first word of the group winning ties.
+    #[serde(default)]
+    pub synonyms: Vec<Vec<String>>,
+
+    /// Severity level for synonym family issues
+    #[serde(default = "default_severity")]
+    pub synonym_severity: String,
+
+    /// Report vars whose service prefix differs from the rest of their cluster
+    #[serde(default = "default_true")]
+    pub service_prefixes: bool,
 }
 
 impl Default for NamingConfig {
@@ -96,10 +252,85 @@ impl Default for NamingConfig {
             builtin_rules: true,
             custom_rules: Vec::new(),
             ignore_patterns: Vec::new(),
+            conventions: ConventionConfig::default(),
+            builtin_synonyms: true,
+            synonyms: Vec::new(),
+            synonym_severity: default_severity(),
+            service_prefixes: true,
         }
     }
 }
 
+impl NamingConfig {
+    pub fn synonym_severity_level(&self) -> Severity {
+        parse_severity(&self.synonym_severity)
+    }
+}
+
+/// Naming conventions checked against every var name. All checks are off
+/// unless configured.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ConventionConfig {
+    /// Names must be SCREAMING_SNAKE_CASE
+    #[serde(default)]
+    pub screaming_snake: bool,
+
+    /// Prefixes names must start with, optionally per directory
+    #[serde(default)]
+    pub prefixes: Vec<PrefixRule>,
+
+    /// Words (between underscores) that must not appear in names
+    #[serde(default)]
+    pub banned_words: Vec<String>,
+
+    /// Maximum name length
+    #[serde(default)]
+    pub max_length: Option<usize>,
+
+    /// Names must not start with a digit
+    #[serde(default)]
+    pub no_leading_digit: bool,
+
+    /// Names must not contain `__`
+    #[serde(default)]
+    pub no_double_underscore: bool,
+
+    /// Severity level for violations
+    #[serde(default = "default_severity")]
+    pub severity: String,
+}
+
+impl Default for ConventionConfig {
+    fn default() -> Self {
+        Self {
+            screaming_snake: false,
+            prefixes: Vec::new(),
+            banned_words: Vec::new(),
+            max_length: None,
+            no_leading_digit: false,
+            no_double_underscore: false,
+            severity: default_severity(),
+        }
+    }
+}
+
+impl ConventionConfig {
+    pub fn severity_level(&self) -> Severity {
+        parse_severity(&self.severity)
+    }
+}
+
+/// Allowed prefixes for names used or defined under a directory
+#[derive(Debug, Clone, Deserialize)]
+pub struct PrefixRule {
+    /// Directory the rule applies to, e.g. "services/billing" (None = everywhere)
+    #[serde(default)]
+    pub path: Option<String>,
+
+    /// Prefixes a name may start with, e.g. ["BILLING_", "STRIPE_"]
+    pub allowed: Vec<String>,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -121,6 +352,11 @@ pub struct NamingRule {
     /// The preferred name to use
     pub preferred: String,
 
+    /// Treat `alternatives` as regexes and `preferred` as a replacement that
+    /// may reference their captures, e.g. `${svc}_DATABASE_URL`
+    #[serde(default)]
+    pub regex: bool,
+
     /// Severity level for this rule
     #[serde(default = "default_severity")]
     pub severity: String,
@@ -132,14 +368,31 @@ fn default_severity() -> String {
 
 impl NamingRule {
     pub fn severity_level(&self) -> Severity {
-        match self.severity.to_lowercase().as_str() {
-            "error" => Severity::Error,
-            "warning" => Severity::Warning,
-            _ => Severity::Info,
-        }
+        parse_severity(&self.severity)
+    }
+}
+
+fn parse_severity(severity: &str) -> Severity {
+    match severity.to_lowercase().as_str() {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => Severity::Info,
     }
 }
 
+/// Configuration for client-side (browser) code checks
+#[derive(Debug, Default, Deserialize)]
+pub struct ClientConfig {
+    /// Frameworks whose public prefix applies: "vite", "cra", "next".
+    /// Empty = detect them from package.json dependencies.
+    #[serde(default)]
+    pub frameworks: Vec<String>,
+
+    /// Globs of files whose code runs in the browser, e.g. "src/client/**"
+    #[serde(default)]
+    pub paths: Vec<String>,
+}
+
 /// Configuration for output formatting
 #[derive(Debug, Deserialize)]
 pub struct OutputConfig {
@@ -197,6 +450,133 @@ impl Config {
         let config: Config = toml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
 
+        regex::Regex::new(&config.scan.name_pattern).with_context(|| {
+            format!(
+                "Invalid name_pattern in {}: {}",
+                path.display(),
+                config.scan.name_pattern
+            )
+        })?;
+
+        for pattern in &config.scan.patterns {
+            regex::Regex::new(&pattern.regex).with_context(|| {
+                format!("Invalid regex in [[scan.patterns]]: {}", pattern.regex)
+            })?;
+
+            let languages: Vec<&str> = Language::all().iter().map(|l| l.id()).collect();
+            if pattern.languages.is_empty() {
+                anyhow::bail!(
+                    "No languages for [[scan.patterns]] {} in {} (expected one or more of: {})",
+                    pattern.regex,
+                    path.display(),
+                    languages.join(", ")
+                );
+            }
+            for language in &pattern.languages {
+                if Language::from_name(language).is_none() {
+                    anyhow::bail!(
+                        "Unknown language in [[scan.patterns]] in {}: {} (expected one of: {})",
+                        path.display(),
+                        language,
+                        languages.join(", ")
+                    );
+                }
+            }
+
+            if !PATTERN_KINDS.contains(&pattern.kind.to_lowercase().as_str()) {
+                anyhow::bail!(
+                    "Unknown kind in [[scan.patterns]] in {}: {} (expected one of: {})",
+                    path.display(),
+                    pattern.kind,
+                    PATTERN_KINDS.join(", ")
+                );
+            }
+        }
+
+        for wrapper in &config.scan.wrappers {
+            let name = wrapper.name.trim_end_matches(['?', '!']);
+            let valid_name = name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+                && name
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+            if !valid_name {
+                anyhow::bail!(
+                    "Invalid name in [[scan.wrappers]] in {}: {:?} (expected a function or method name)",
+                    path.display(),
+                    wrapper.name
+                );
+            }
+
+            for language in &wrapper.languages {
+                if Language::from_name(language).is_none() {
+                    let languages: Vec<&str> = Language::all().iter().map(|l| l.id()).collect();
+                    anyhow::bail!(
+                        "Unknown language for [[scan.wrappers]] {} in {}: {} (expected one of: {})",
+                        wrapper.name,
+                        path.display(),
+                        language,
+                        languages.join(", ")
+                    );
+                }
+            }
+        }
+
+        for rule in config.naming.custom_rules.iter().filter(|r| r.regex) {
+            for alternative in &rule.alternatives {
+                regex::Regex::new(alternative).with_context(|| {
+                    format!(
+                        "Invalid regex in naming rule '{}': {}",
+                        rule.name, alternative
+                    )
+                })?;
+            }
+        }
+
+        match config.scan.parser.as_str() {
+            "regex" => {}
+            "ast" if cfg!(feature = "ast") => {}
+            "ast" => anyhow::bail!(
+                "parser = \"ast\" in {} needs env-audit built with `--features ast`",
+                path.display()
+            ),
+            other => anyhow::bail!(
+                "Unknown parser in {}: {} (expected \"regex\" or \"ast\")",
+                path.display(),
+                other
+            ),
+        }
+
+        for preset in &config.scan.template_presets {
+            if !TEMPLATE_PRESETS.contains(&preset.to_lowercase().as_str()) {
+                anyhow::bail!(
+                    "Unknown template preset in {}: {} (expected one of: {})",
+                    path.display(),
+                    preset,
+                    TEMPLATE_PRESETS.join(", ")
+                );
+            }
+        }
+
+        for template in &config.scan.templates {
+            if template.open.is_empty() || template.close.is_empty() {
+                anyhow::bail!(
+                    "Empty delimiter in [[scan.templates]] in {}: open and close must both be set",
+                    path.display()
+                );
+            }
+        }
+
+        for framework in &config.client.frameworks {
+            if ClientFramework::from_name(framework).is_none() {
+                anyhow::bail!(
+                    "Unknown client framework in {}: {} (expected one of: {})",
+                    path.display(),
+                    framework,
+                    CLIENT_FRAMEWORKS.join(", ")
+                );
+            }
+        }
+
         Ok(config)
     }
 
@@ -224,6 +604,50 @@ exclude = [
 # Languages to scan (comment out for all supported languages)
 # languages = ["javascript", "typescript", "python", "rust", "go", "ruby", "php", "java", "csharp"]
 
+# Treat Java System.getProperty() lookups as env vars (they are JVM properties by default)
+java_system_properties = false
+
+# Cloudflare Workers: read [vars] and bindings from wrangler.toml and secrets
+# from .dev.vars, and report env.X in Worker entrypoints as usages
+workers = false
+
+# Scanning engine: "regex" (fast default) or "ast" (tree-sitter, needs the `ast` cargo feature)
+parser = "regex"
+
+# Regex that detected var names must match; use "^[A-Z_][A-Z0-9_]*$" for uppercase only
+name_pattern = "^[A-Za-z_][A-Za-z0-9_]*$"
+
+# Custom patterns for project-specific env helpers
+# [[scan.patterns]]
+# languages = ["python"]
+# regex = 'cfg\.get\("(?P<name>[A-Z_]+)"'
+# kind = "read"
+# extensions = ["pyi"]
+
+# Files holding env key constants (`export const STRIPE = "STRIPE_KEY"`), so
+# `process.env[Keys.STRIPE]` elsewhere resolves to STRIPE_KEY
+# key_modules = ["src/config/env-keys.ts"]
+
+# Template placeholders: %VITE_X% and %REACT_APP_X% in HTML, env_var('X') in
+# Jinja, lookup('env', 'X') in Ansible. None are enabled by default.
+# template_presets = ["vite", "cra", "jinja", "ansible"]
+
+# Custom placeholder delimiters
+# [[scan.templates]]
+# open = "${"
+# close = "}"
+# extensions = ["tmpl"]
+# prefix = "APP_"
+
+# Helpers that take an env var name; functions whose parameter is used as an
+# env key (`process.env[name]`) are detected automatically
+# [[scan.wrappers]]
+# name = "requireEnv"
+# languages = ["javascript", "typescript"]
+# arg = 0
+# default_arg = 1
+# required = true
+
 [naming]
 # Use built-in naming conflict rules
 builtin_rules = true
@@ -231,6 +655,16 @@ builtin_rules = true
 # Patterns to ignore (regex) - vars matching these won't trigger naming issues
 ignore_patterns = ["^_", "^INTERNAL_"]
 
+# Words that mean the same thing; names differing only by these are
+# reported as one family. Extends the built-in groups (DB/DATABASE, ...).
+# The spelling the project uses most is suggested, the first word on a tie.
+builtin_synonyms = true
+# synonyms = [["KUBERNETES", "K8S"]]
+# synonym_severity = "warning"
+
+# Flag vars like PAYMENTS_STRIPE_WEBHOOK when most STRIPE vars start with STRIPE_
+service_prefixes = true
+
 # Custom naming rules
 # [[naming.custom_rules]]
 # name = "database-url"
@@ -238,6 +672,35 @@ ignore_patterns = ["^_", "^INTERNAL_"]
 # alternatives = ["DB_URL", "DB_CONNECTION"]
 # preferred = "DATABASE_URL"
 # severity = "warning"
+#
+# Regex rule: alternatives are regexes, preferred can use their captures
+# [[naming.custom_rules]]
+# name = "service-database-url"
+# alternatives = ['^(?P<svc>\w+?)_DB_(?:URL|HOST)$']
+# preferred = "${svc}_DATABASE_URL"
+# regex = true
+
+# Style conventions; every check is off unless set
+# [naming.conventions]
+# screaming_snake = true
+# max_length = 40
+# banned_words = ["TEMP", "NEW"]
+# no_leading_digit = true
+# no_double_underscore = true
+# severity = "warning"
+#
+# [[naming.conventions.prefixes]]
+# path = "services/billing"
+# allowed = ["BILLING_", "STRIPE_"]
+
+[client]
+# Frameworks that only expose prefixed vars to the browser: "vite" (VITE_),
+# "cra" (REACT_APP_), "next" (NEXT_PUBLIC_). Detected from package.json if empty.
+frameworks = []
+
+# Files whose code runs in the browser, in addition to Next.js "use client"
+# files and everything under src/ in Create React App projects
+# paths = ["src/client/**"]
 
 [output]
 # Default output format: "terminal", "json", "markdown", "html"
@@ -0,0 +1,1039 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::Path;
+use tree_sitter::{Node, Parser, Point, Tree};
+
+use super::expansion::scan_expansions;
+use super::{LanguageScanner, ScanContext};
+use crate::types::{EnvVarUsage, Language};
+
+/// Accurate scanning mode built on tree-sitter, selected with `parser = "ast"`.
+///
+/// Wraps a regex scanner. Direct env reads (`process.env.X`, `os.getenv`,
+/// `env!`, `System.getenv`, ...) are taken from the syntax tree, so aliases,
+/// multi-line calls and commented-out code are handled and spans are exact.
+/// Framework patterns only the wrapped scanner knows about are still reported,
+/// except for matches inside comments or env reads quoted inside strings.
+pub struct AstScanner {
+    inner: Box<dyn LanguageScanner>,
+}
+
+static VAR_NAME: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_]*$").unwrap());
+
+// String text ending in an env read, as in "set process.env.X" or
+// "os.environ['X']"; keys such as env('X') start the string instead
+static QUOTED_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?:\benv|\bENV|\benviron|[Gg]etenv|GetEnvironmentVariable|env::var(?:_os)?)\s*(?:\?\.|\.get\(|\.fetch\(|[.\[(])\s*['"]?$"#,
+    )
+    .unwrap()
+});
+
+/// Bundled grammars, chosen by file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Grammar {
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Python,
+    Rust,
+    Go,
+    Ruby,
+    Php,
+    Java,
+    CSharp,
+}
+
+impl Grammar {
+    fn for_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_lowercase();
+        Some(match ext.as_str() {
+            "js" | "mjs" | "cjs" | "jsx" => Grammar::JavaScript,
+            "ts" | "mts" | "cts" => Grammar::TypeScript,
+            "tsx" => Grammar::Tsx,
+            "py" => Grammar::Python,
+            "rs" => Grammar::Rust,
+            "go" => Grammar::Go,
+            "rb" => Grammar::Ruby,
+            "php" => Grammar::Php,
+            "java" => Grammar::Java,
+            "cs" => Grammar::CSharp,
+            _ => return None,
+        })
+    }
+
+    fn parse(self, content: &str) -> Option<Tree> {
+        let language: tree_sitter::Language = match self {
+            Grammar::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Grammar::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Grammar::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Grammar::Python => tree_sitter_python::LANGUAGE.into(),
+            Grammar::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Grammar::Go => tree_sitter_go::LANGUAGE.into(),
+            Grammar::Ruby => tree_sitter_ruby::LANGUAGE.into(),
+            Grammar::Php => tree_sitter_php::LANGUAGE_PHP.into(),
+            Grammar::Java => tree_sitter_java::LANGUAGE.into(),
+            Grammar::CSharp => tree_sitter_c_sharp::LANGUAGE.into(),
+        };
+
+        let mut parser = Parser::new();
+        parser.set_language(&language).ok()?;
+        parser.parse(content, None)
+    }
+}
+
+impl AstScanner {
+    pub fn new(inner: Box<dyn LanguageScanner>) -> Self {
+        Self { inner }
+    }
+}
+
+impl LanguageScanner for AstScanner {
+    fn language(&self) -> Language {
+        self.inner.language()
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        self.inner.extensions()
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        self.inner.matches_file(path)
+    }
+
+    fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        self.scan_with_context(content, file_path, &ScanContext::default())
+    }
+
+    fn collect_context(&self, content: &str, file_path: &Path, context: &mut ScanContext) {
+        self.inner.collect_context(content, file_path, context);
+    }
+
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
+        let regex_usages = self.inner.scan_with_context(content, file_path, context);
+
+        let Some(grammar) = Grammar::for_path(file_path) else {
+            return regex_usages;
+        };
+        let Some(tree) = grammar.parse(content) else {
+            return regex_usages;
+        };
+
+        let mut collector = Collector::new(content, file_path, self.inner.language());
+        collector.collect(grammar, tree.root_node());
+        let mut usages = collector.usages;
+
+        // Keep what only the regex scanner knows about, unless it is commented
+        // out or quoted. References in expanded strings belong in strings.
+        let found: HashSet<(String, usize)> =
+            usages.iter().map(|u| (u.name.clone(), u.line)).collect();
+        let expanded: HashSet<(String, usize)> =
+            scan_expansions(self.inner.language(), content, file_path)
+                .into_iter()
+                .map(|u| (u.name, u.line))
+                .collect();
+        usages.extend(regex_usages.into_iter().filter(|u| {
+            let key = (u.name.clone(), u.line);
+            !found.contains(&key)
+                && !in_comment(&tree, u)
+                && (expanded.contains(&key) || !in_string(&tree, content, u))
+        }));
+
+        usages.sort_by_key(|u| (u.line, u.column));
+        usages
+    }
+}
+
+fn in_comment(tree: &Tree, usage: &EnvVarUsage) -> bool {
+    let point = Point {
+        row: usage.line.saturating_sub(1),
+        column: usage.column.saturating_sub(1),
+    };
+    let mut node = tree.root_node().descendant_for_point_range(point, point);
+    while let Some(n) = node {
+        if n.kind().contains("comment") {
+            return true;
+        }
+        node = n.parent();
+    }
+    false
+}
+
+/// True when the usage is an env read written inside a string literal, such
+/// as `"see process.env.X"`. Interpolated code (`${..}`, `#{..}`) is not
+/// part of the string.
+fn in_string(tree: &Tree, content: &str, usage: &EnvVarUsage) -> bool {
+    let point = Point {
+        row: usage.line.saturating_sub(1),
+        column: usage.column.saturating_sub(1),
+    };
+    let Some(line) = content.lines().nth(point.row) else {
+        return false;
+    };
+    let mut node = tree.root_node().descendant_for_point_range(point, point);
+    while let Some(n) = node {
+        let kind = n.kind();
+        if kind.contains("substitution") || kind.contains("interpolation") {
+            return false;
+        }
+        if kind.contains("string") {
+            let start = if n.start_position().row == point.row {
+                n.start_position().column
+            } else {
+                0
+            };
+            return line
+                .get(start..point.column)
+                .is_some_and(|before| QUOTED_ACCESS.is_match(before));
+        }
+        node = n.parent();
+    }
+    false
+}
+
+fn walk<'t>(node: Node<'t>, visit: &mut impl FnMut(Node<'t>)) {
+    visit(node);
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        walk(child, visit);
+    }
+}
+
+/// Named children of a node, skipping comments
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    node.named_children(&mut cursor)
+        .filter(|n| !n.kind().contains("comment"))
+        .collect()
+}
+
+/// Call arguments, unwrapping PHP and C# `argument` nodes
+fn arguments<'t>(call: Node<'t>, field: &str) -> Vec<Node<'t>> {
+    call.child_by_field_name(field)
+        .map(named_children)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|arg| match arg.kind() {
+            "argument" => arg
+                .named_child(arg.named_child_count().saturating_sub(1))
+                .unwrap_or(arg),
+            _ => arg,
+        })
+        .collect()
+}
+
+/// Collects usages from a syntax tree
+struct Collector<'a> {
+    content: &'a str,
+    lines: Vec<&'a str>,
+    file_path: &'a Path,
+    language: Language,
+    usages: Vec<EnvVarUsage>,
+}
+
+impl<'a> Collector<'a> {
+    fn new(content: &'a str, file_path: &'a Path, language: Language) -> Self {
+        Self {
+            content,
+            lines: content.lines().collect(),
+            file_path,
+            language,
+            usages: Vec::new(),
+        }
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        &self.content[node.byte_range()]
+    }
+
+    /// Node text with whitespace removed, for comparing member chains
+    fn compact(&self, node: Node) -> String {
+        self.text(node)
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect()
+    }
+
+    /// Contents of a single-line string literal and where they start
+    fn literal(&self, node: Node) -> Option<(&'a str, Point)> {
+        let text = self.text(node);
+        let quote_at = text.find(['"', '\'', '`'])?;
+        // Allow prefixes such as r"", b'', @""
+        if !text[..quote_at]
+            .chars()
+            .all(|c| c.is_ascii_alphabetic() || c == '@')
+        {
+            return None;
+        }
+        let quote = text[quote_at..].chars().next()?;
+        let inner = text[quote_at + 1..].strip_suffix(quote)?;
+        if inner.contains('\n') {
+            return None;
+        }
+
+        let start = node.start_position();
+        Some((
+            inner,
+            Point {
+                row: start.row,
+                column: start.column + quote_at + 1,
+            },
+        ))
+    }
+
+    /// A fallback value as written in the code, unquoted if it is a string
+    fn value(&self, node: Node) -> String {
+        match self.literal(node) {
+            Some((inner, _)) => inner.to_string(),
+            None => self.text(node).to_string(),
+        }
+    }
+
+    fn push(
+        &mut self,
+        name: &str,
+        at: Point,
+        default_value: Option<String>,
+        required: Option<bool>,
+    ) {
+        if !VAR_NAME.is_match(name) {
+            return;
+        }
+        self.usages.push(EnvVarUsage {
+            name: name.to_string(),
+            file_path: self.file_path.to_path_buf(),
+            line: at.row + 1,
+            column: at.column + 1,
+            language: self.language,
+            context: self.lines.get(at.row).map(|l| l.trim().to_string()),
+            default_value,
+            required,
+            exposure: None,
+        });
+    }
+
+    /// Push a usage named by a string literal argument
+    fn push_literal(
+        &mut self,
+        node: Option<Node>,
+        default_value: Option<String>,
+        required: Option<bool>,
+    ) {
+        if let Some((name, at)) = node.and_then(|n| self.literal(n)) {
+            self.push(name, at, default_value, required);
+        }
+    }
+
+    fn collect(&mut self, grammar: Grammar, root: Node) {
+        match grammar {
+            Grammar::JavaScript | Grammar::TypeScript | Grammar::Tsx => self.javascript(root),
+            Grammar::Python => self.python(root),
+            Grammar::Rust => self.rust(root),
+            Grammar::Go => self.go(root),
+            Grammar::Ruby => self.ruby(root),
+            Grammar::Php => self.php(root),
+            Grammar::Java => self.java(root),
+            Grammar::CSharp => self.csharp(root),
+        }
+    }
+
+    fn javascript(&mut self, root: Node) {
+        // Local names bound to process.env
+        let mut aliases = HashSet::new();
+        walk(root, &mut |node| match node.kind() {
+            // import { env } from 'process' / import { env as E } from 'node:process'
+            "import_statement" => {
+                let from_process = node
+                    .child_by_field_name("source")
+                    .and_then(|s| self.literal(s))
+                    .is_some_and(|(s, _)| s == "process" || s == "node:process");
+                if from_process {
+                    walk(node, &mut |n| {
+                        if n.kind() == "import_specifier"
+                            && n.child_by_field_name("name")
+                                .is_some_and(|name| self.text(name) == "env")
+                        {
+                            let local = n
+                                .child_by_field_name("alias")
+                                .or_else(|| n.child_by_field_name("name"))
+                                .unwrap();
+                            aliases.insert(self.text(local).to_string());
+                        }
+                    });
+                }
+            }
+            "variable_declarator" => {
+                let (Some(name), Some(value)) = (
+                    node.child_by_field_name("name"),
+                    node.child_by_field_name("value"),
+                ) else {
+                    return;
+                };
+                match name.kind() {
+                    // const env = process.env
+                    "identifier" if self.is_js_env(value, &HashSet::new()) => {
+                        aliases.insert(self.text(name).to_string());
+                    }
+                    // const { env } = process / const { env: E } = process
+                    "object_pattern" if self.text(value) == "process" => {
+                        for prop in named_children(name) {
+                            match prop.kind() {
+                                "shorthand_property_identifier_pattern"
+                                    if self.text(prop) == "env" =>
+                                {
+                                    aliases.insert("env".to_string());
+                                }
+                                "pair_pattern" => {
+                                    let key = prop.child_by_field_name("key");
+                                    let local = prop.child_by_field_name("value");
+                                    if let (Some(key), Some(local)) = (key, local) {
+                                        if self.text(key) == "env" && local.kind() == "identifier" {
+                                            aliases.insert(self.text(local).to_string());
+                                        }
+                                    }
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        });
+
+        walk(root, &mut |node| match node.kind() {
+            // process.env.X, process.env?.X, Bun.env.X
+            "member_expression" => {
+                let object = node.child_by_field_name("object");
+                let property = node.child_by_field_name("property");
+                if let (Some(object), Some(property)) = (object, property) {
+                    if property.kind() == "property_identifier" && self.is_js_env(object, &aliases)
+                    {
+                        let default_value = self.js_default(node);
+                        self.push(
+                            self.text(property),
+                            property.start_position(),
+                            default_value,
+                            None,
+                        );
+                    }
+                }
+            }
+            // Deno.env.get("X")
+            "call_expression"
+                if node
+                    .child_by_field_name("function")
+                    .is_some_and(|f| self.js_chain(f) == "Deno.env.get") =>
+            {
+                let default_value = self.js_default(node);
+                let name = arguments(node, "arguments").first().copied();
+                self.push_literal(name, default_value, None);
+            }
+            // process.env["X"]
+            "subscript_expression"
+                if node
+                    .child_by_field_name("object")
+                    .is_some_and(|o| self.is_js_env(o, &aliases)) =>
+            {
+                let default_value = self.js_default(node);
+                self.push_literal(node.child_by_field_name("index"), default_value, None);
+            }
+            // const { A, B = "x", C: c } = process.env
+            "variable_declarator" => {
+                let (Some(name), Some(value)) = (
+                    node.child_by_field_name("name"),
+                    node.child_by_field_name("value"),
+                ) else {
+                    return;
+                };
+                if name.kind() != "object_pattern" || !self.is_js_env(value, &aliases) {
+                    return;
+                }
+                for prop in named_children(name) {
+                    match prop.kind() {
+                        "shorthand_property_identifier_pattern" => {
+                            self.push(self.text(prop), prop.start_position(), None, None);
+                        }
+                        "object_assignment_pattern" => {
+                            if let Some(left) = prop.child_by_field_name("left") {
+                                let default_value =
+                                    prop.child_by_field_name("right").map(|r| self.value(r));
+                                self.push(
+                                    self.text(left),
+                                    left.start_position(),
+                                    default_value,
+                                    None,
+                                );
+                            }
+                        }
+                        "pair_pattern" => {
+                            let Some(key) = prop.child_by_field_name("key") else {
+                                continue;
+                            };
+                            let default_value = prop
+                                .child_by_field_name("value")
+                                .filter(|v| v.kind() == "assignment_pattern")
+                                .and_then(|v| v.child_by_field_name("right"))
+                                .map(|r| self.value(r));
+                            match self.literal(key) {
+                                Some((name, at)) => self.push(name, at, default_value, None),
+                                None => self.push(
+                                    self.text(key),
+                                    key.start_position(),
+                                    default_value,
+                                    None,
+                                ),
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        });
+    }
+
+    fn is_js_env(&self, node: Node, aliases: &HashSet<String>) -> bool {
+        match self.js_chain(node).as_str() {
+            "process.env" | "import.meta.env" | "Bun.env" | "Deno.env.toObject()" => true,
+            name => node.kind() == "identifier" && aliases.contains(name),
+        }
+    }
+
+    /// Member chain without optional chaining or a `globalThis.` prefix
+    fn js_chain(&self, node: Node) -> String {
+        let chain = self.compact(node).replace("?.", ".");
+        match chain.strip_prefix("globalThis.") {
+            Some(rest) => rest.to_string(),
+            None => chain,
+        }
+    }
+
+    /// `process.env.X || "fallback"` / `process.env.X ?? 3000`
+    fn js_default(&self, node: Node) -> Option<String> {
+        let parent = node.parent()?;
+        if parent.kind() != "binary_expression" || parent.child_by_field_name("left")? != node {
+            return None;
+        }
+        let operator = self.text(parent.child_by_field_name("operator")?);
+        if operator != "||" && operator != "??" {
+            return None;
+        }
+        parent.child_by_field_name("right").map(|r| self.value(r))
+    }
+
+    fn python(&mut self, root: Node) {
+        let mut os_modules: HashSet<String> = HashSet::from(["os".to_string()]);
+        let mut environ_names = HashSet::new();
+        let mut getenv_names = HashSet::new();
+
+        walk(root, &mut |node| match node.kind() {
+            // import os as o
+            "import_statement" => {
+                for child in named_children(node) {
+                    if child.kind() == "aliased_import"
+                        && child
+                            .child_by_field_name("name")
+                            .is_some_and(|n| self.text(n) == "os")
+                    {
+                        if let Some(alias) = child.child_by_field_name("alias") {
+                            os_modules.insert(self.text(alias).to_string());
+                        }
+                    }
+                }
+            }
+            // from os import environ as E, getenv
+            "import_from_statement" => {
+                if node
+                    .child_by_field_name("module_name")
+                    .is_none_or(|m| self.text(m) != "os")
+                {
+                    return;
+                }
+                let mut cursor = node.walk();
+                for imported in node.children_by_field_name("name", &mut cursor) {
+                    let (name, local) = match imported.kind() {
+                        "aliased_import" => (
+                            imported.child_by_field_name("name"),
+                            imported.child_by_field_name("alias"),
+                        ),
+                        _ => (Some(imported), Some(imported)),
+                    };
+                    let (Some(name), Some(local)) = (name, local) else {
+                        continue;
+                    };
+                    let local = self.text(local).to_string();
+                    match self.text(name) {
+                        "environ" => {
+                            environ_names.insert(local);
+                        }
+                        "getenv" => {
+                            getenv_names.insert(local);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        });
+
+        let is_environ = |node: Node| match node.kind() {
+            "identifier" => environ_names.contains(self.text(node)),
+            "attribute" => {
+                node.child_by_field_name("object")
+                    .is_some_and(|o| os_modules.contains(self.text(o)))
+                    && node
+                        .child_by_field_name("attribute")
+                        .is_some_and(|a| self.text(a) == "environ")
+            }
+            _ => false,
+        };
+
+        let mut found = Vec::new();
+        walk(root, &mut |node| match node.kind() {
+            // os.environ["X"]
+            "subscript" if node.child_by_field_name("value").is_some_and(is_environ) => {
+                found.push((node.child_by_field_name("subscript"), None));
+            }
+            // os.getenv("X", d), getenv("X"), os.environ.get("X", default=d)
+            "call" => {
+                let Some(function) = node.child_by_field_name("function") else {
+                    return;
+                };
+                let is_getter = match function.kind() {
+                    "identifier" => getenv_names.contains(self.text(function)),
+                    "attribute" => {
+                        let object = function.child_by_field_name("object");
+                        let attribute = function
+                            .child_by_field_name("attribute")
+                            .map(|a| self.text(a));
+                        match (object, attribute) {
+                            (Some(o), Some("getenv")) => os_modules.contains(self.text(o)),
+                            (Some(o), Some("get")) => is_environ(o),
+                            _ => false,
+                        }
+                    }
+                    _ => false,
+                };
+                if !is_getter {
+                    return;
+                }
+
+                let args = arguments(node, "arguments");
+                let default_value = args
+                    .iter()
+                    .find(|a| {
+                        a.kind() == "keyword_argument"
+                            && a.child_by_field_name("name")
+                                .is_some_and(|n| self.text(n) == "default")
+                    })
+                    .and_then(|a| a.child_by_field_name("value"))
+                    .or_else(|| {
+                        args.get(1)
+                            .filter(|a| a.kind() != "keyword_argument")
+                            .copied()
+                    })
+                    .map(|d| self.value(d));
+                found.push((args.first().copied(), default_value));
+            }
+            _ => {}
+        });
+
+        for (node, default_value) in found {
+            self.push_literal(node, default_value, None);
+        }
+    }
+
+    fn rust(&mut self, root: Node) {
+        let mut found = Vec::new();
+        walk(root, &mut |node| match node.kind() {
+            // env!("X"), option_env!("X")
+            "macro_invocation" => {
+                let name = node
+                    .child_by_field_name("macro")
+                    .map(|m| self.text(m).rsplit("::").next().unwrap_or_default());
+                let required = match name {
+                    Some("env") => true,
+                    Some("option_env") => false,
+                    _ => return,
+                };
+                let first = node
+                    .named_children(&mut node.walk())
+                    .find(|c| c.kind() == "token_tree")
+                    .and_then(|t| t.named_child(0));
+                found.push((first, Some(required)));
+            }
+            // env::var("X"), std::env::var_os("X"), dotenvy::var("X")
+            "call_expression" => {
+                let path = node
+                    .child_by_field_name("function")
+                    .map(|f| self.compact(f))
+                    .unwrap_or_default();
+                if ["env::var", "env::var_os", "dotenvy::var", "dotenv::var"]
+                    .iter()
+                    .any(|p| path == *p || path.ends_with(&format!("::{}", p)))
+                {
+                    found.push((arguments(node, "arguments").first().copied(), None));
+                }
+            }
+            _ => {}
+        });
+
+        for (node, required) in found {
+            self.push_literal(node, None, required);
+        }
+    }
+
+    fn go(&mut self, root: Node) {
+        // import o "os"
+        let mut os_packages: HashSet<String> = HashSet::from(["os".to_string()]);
+        walk(root, &mut |node| {
+            if node.kind() == "import_spec"
+                && node
+                    .child_by_field_name("path")
+                    .and_then(|p| self.literal(p))
+                    .is_some_and(|(p, _)| p == "os")
+            {
+                if let Some(name) = node.child_by_field_name("name") {
+                    os_packages.insert(self.text(name).to_string());
+                }
+            }
+        });
+
+        let mut found = Vec::new();
+        walk(root, &mut |node| {
+            if node.kind() != "call_expression" {
+                return;
+            }
+            let Some(function) = node.child_by_field_name("function") else {
+                return;
+            };
+            let package = function
+                .child_by_field_name("operand")
+                .map(|o| self.text(o));
+            let func = function.child_by_field_name("field").map(|f| self.text(f));
+            if function.kind() == "selector_expression"
+                && package.is_some_and(|p| os_packages.contains(p))
+                && matches!(func, Some("Getenv") | Some("LookupEnv"))
+            {
+                found.push(arguments(node, "arguments").first().copied());
+            }
+        });
+
+        for node in found {
+            self.push_literal(node, None, None);
+        }
+    }
+
+    fn ruby(&mut self, root: Node) {
+        let mut found = Vec::new();
+        walk(root, &mut |node| match node.kind() {
+            // ENV["X"]
+            "element_reference" => {
+                let is_env = node
+                    .child_by_field_name("object")
+                    .is_some_and(|o| matches!(self.compact(o).as_str(), "ENV" | "::ENV"));
+                if is_env {
+                    found.push((named_children(node).get(1).copied(), None, None));
+                }
+            }
+            // ENV.fetch("X", d), ENV.fetch("X") { d }, ENV.key?("X")
+            "call" => {
+                let is_env = node
+                    .child_by_field_name("receiver")
+                    .is_some_and(|r| matches!(self.compact(r).as_str(), "ENV" | "::ENV"));
+                if !is_env {
+                    return;
+                }
+                let args = arguments(node, "arguments");
+                match node.child_by_field_name("method").map(|m| self.text(m)) {
+                    Some("fetch") => {
+                        let default_value = args.get(1).map(|d| self.value(*d));
+                        // Without a fallback, fetch raises KeyError
+                        let required =
+                            default_value.is_none() && node.child_by_field_name("block").is_none();
+                        found.push((args.first().copied(), default_value, Some(required)));
+                    }
+                    Some("key?") | Some("include?") | Some("has_key?") | Some("member?") => {
+                        found.push((args.first().copied(), None, Some(false)));
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        });
+
+        for (node, default_value, required) in found {
+            self.push_literal(node, default_value, required);
+        }
+    }
+
+    fn php(&mut self, root: Node) {
+        let mut found = Vec::new();
+        walk(root, &mut |node| match node.kind() {
+            // getenv('X'), env('X', 'default')
+            "function_call_expression" => {
+                let name = node
+                    .child_by_field_name("function")
+                    .map(|f| self.text(f).trim_start_matches('\\'));
+                let args = arguments(node, "arguments");
+                match name {
+                    Some("getenv") => found.push((args.first().copied(), None)),
+                    Some("env") => {
+                        let default_value = args.get(1).map(|d| self.value(*d));
+                        found.push((args.first().copied(), default_value));
+                    }
+                    _ => {}
+                }
+            }
+            // $_ENV['X'], $_SERVER['X']
+            "subscript_expression" => {
+                let children = named_children(node);
+                if children
+                    .first()
+                    .is_some_and(|v| matches!(self.text(*v), "$_ENV" | "$_SERVER"))
+                {
+                    found.push((children.get(1).copied(), None));
+                }
+            }
+            _ => {}
+        });
+
+        for (node, default_value) in found {
+            self.push_literal(node, default_value, None);
+        }
+    }
+
+    fn java(&mut self, root: Node) {
+        let mut found = Vec::new();
+        walk(root, &mut |node| {
+            if node.kind() == "method_invocation"
+                && node.child_by_field_name("object").is_some_and(|o| {
+                    matches!(self.compact(o).as_str(), "System" | "java.lang.System")
+                })
+                && node
+                    .child_by_field_name("name")
+                    .is_some_and(|n| self.text(n) == "getenv")
+            {
+                found.push(arguments(node, "arguments").first().copied());
+            }
+        });
+
+        for node in found {
+            self.push_literal(node, None, None);
+        }
+    }
+
+    fn csharp(&mut self, root: Node) {
+        let mut found = Vec::new();
+        walk(root, &mut |node| {
+            if node.kind() == "invocation_expression"
+                && node
+                    .child_by_field_name("function")
+                    .map(|f| self.compact(f))
+                    .is_some_and(|f| {
+                        f == "Environment.GetEnvironmentVariable"
+                            || f.ends_with(".Environment.GetEnvironmentVariable")
+                    })
+            {
+                found.push(arguments(node, "arguments").first().copied());
+            }
+        });
+
+        for node in found {
+            self.push_literal(node, None, None);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::languages::{
+        CSharpScanner, GoScanner, JavaScanner, JavaScriptScanner, PhpScanner, PythonScanner,
+        RubyScanner, RustScanner,
+    };
+
+    fn scan(inner: Box<dyn LanguageScanner>, file: &str, content: &str) -> Vec<EnvVarUsage> {
+        AstScanner::new(inner).scan(content, Path::new(file))
+    }
+
+    fn names(usages: &[EnvVarUsage]) -> Vec<&str> {
+        usages.iter().map(|u| u.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_grammars_load() {
+        for file in [
+            "a.js", "a.ts", "a.tsx", "a.py", "a.rs", "a.go", "a.rb", "a.php", "a.java", "a.cs",
+        ] {
+            let grammar = Grammar::for_path(Path::new(file)).unwrap();
+            assert!(grammar.parse("").is_some(), "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_javascript_aliases_and_comments() {
+        let content = r#"import { env as E } from 'node:process';
+const { env } = process;
+// process.env.COMMENTED_OUT
+const url = E.DATABASE_URL;
+const port = env.PORT ?? 3000;
+const key = process
+  .env
+  .API_KEY;
+const { HOST = "localhost", MODE: mode } = process.env;
+"#;
+        let usages = scan(Box::new(JavaScriptScanner::new()), "app.ts", content);
+        assert_eq!(
+            names(&usages),
+            vec!["DATABASE_URL", "PORT", "API_KEY", "HOST", "MODE"]
+        );
+        assert_eq!(usages[1].default_value.as_deref(), Some("3000"));
+        assert_eq!((usages[2].line, usages[2].column), (8, 4));
+        assert_eq!(usages[3].default_value.as_deref(), Some("localhost"));
+    }
+
+    #[test]
+    fn test_javascript_runtimes() {
+        let content = r#"const port = Deno.env.get("PORT") ?? "8000";
+const { KV_PATH } = Deno.env.toObject();
+const token = Bun.env.BUN_TOKEN;
+const level = globalThis.process?.env?.LOG_LEVEL;
+"#;
+        let usages = scan(Box::new(JavaScriptScanner::new()), "main.ts", content);
+        assert_eq!(
+            names(&usages),
+            vec!["PORT", "KV_PATH", "BUN_TOKEN", "LOG_LEVEL"]
+        );
+        assert_eq!(usages[0].default_value.as_deref(), Some("8000"));
+    }
+
+    #[test]
+    fn test_python_aliases() {
+        let content = r#"import os as o
+from os import environ as E, getenv
+# os.environ["COMMENTED_OUT"]
+a = E["SECRET_KEY"]
+b = o.getenv("DEBUG", "false")
+c = E.get(
+    "PORT",
+    default="8000",
+)
+d = getenv("HOME")
+"#;
+        let usages = scan(Box::new(PythonScanner::new()), "settings.py", content);
+        assert_eq!(names(&usages), vec!["SECRET_KEY", "DEBUG", "PORT", "HOME"]);
+        assert_eq!(usages[1].default_value.as_deref(), Some("false"));
+        assert_eq!((usages[2].line, usages[2].column), (7, 6));
+        assert_eq!(usages[2].default_value.as_deref(), Some("8000"));
+    }
+
+    #[test]
+    fn test_rust_and_go() {
+        let rust = r#"// env::var("COMMENTED_OUT")
+let a = std::env::var(
+    "DATABASE_URL",
+);
+let b = option_env!("BUILD_ID");
+"#;
+        let usages = scan(Box::new(RustScanner::new()), "main.rs", rust);
+        assert_eq!(names(&usages), vec!["DATABASE_URL", "BUILD_ID"]);
+        assert_eq!(usages[1].required, Some(false));
+
+        let go = r#"package main
+
+import sys "os"
+
+func main() {
+	port := sys.Getenv("PORT")
+}
+"#;
+        let usages = scan(Box::new(GoScanner::new()), "main.go", go);
+        assert_eq!(names(&usages), vec!["PORT"]);
+    }
+
+    #[test]
+    fn test_ruby_and_php() {
+        let ruby = r#"# ENV["COMMENTED_OUT"]
+a = ENV.fetch("REDIS_URL", "redis://localhost")
+b = ENV.fetch("SECRET_KEY_BASE")
+"#;
+        let usages = scan(Box::new(RubyScanner::new()), "app.rb", ruby);
+        assert_eq!(names(&usages), vec!["REDIS_URL", "SECRET_KEY_BASE"]);
+        assert_eq!(
+            usages[0].default_value.as_deref(),
+            Some("redis://localhost")
+        );
+        assert_eq!(usages[1].required, Some(true));
+
+        let php = r#"<?php
+// getenv('COMMENTED_OUT');
+$a = getenv('DB_HOST');
+$b = $_ENV['APP_KEY'];
+$c = env('APP_DEBUG', false);
+"#;
+        let usages = scan(Box::new(PhpScanner::new()), "index.php", php);
+        assert_eq!(names(&usages), vec!["DB_HOST", "APP_KEY", "APP_DEBUG"]);
+        assert_eq!(usages[2].default_value.as_deref(), Some("false"));
+    }
+
+    #[test]
+    fn test_java_and_csharp() {
+        let java = r#"class App {
+    // System.getenv("COMMENTED_OUT")
+    String url = System.getenv("DATABASE_URL");
+}
+"#;
+        let usages = scan(Box::new(JavaScanner::new()), "App.java", java);
+        assert_eq!(names(&usages), vec!["DATABASE_URL"]);
+
+        let csharp = r#"class App {
+    /* Environment.GetEnvironmentVariable("COMMENTED_OUT") */
+    string key = System.Environment.GetEnvironmentVariable("API_KEY");
+}
+"#;
+        let usages = scan(Box::new(CSharpScanner::new()), "App.cs", csharp);
+        assert_eq!(names(&usages), vec!["API_KEY"]);
+    }
+
+    #[test]
+    fn test_quoted_reads_are_dropped() {
+        let content = r#"console.log("set process.env.DEBUG_TOKEN to enable");
+const greeting = `hi ${process.env.USER_NAME}`;
+"#;
+        let usages = scan(Box::new(JavaScriptScanner::new()), "app.js", content);
+        assert_eq!(names(&usages), vec!["USER_NAME"]);
+
+        let content = r#"help = "read os.environ['SECRET_KEY'] first"
+home = os.path.expandvars("$APP_HOME/data")
+"#;
+        let usages = scan(Box::new(PythonScanner::new()), "app.py", content);
+        assert_eq!(names(&usages), vec!["APP_HOME"]);
+
+        // Keys and placeholders passed as strings stay
+        let content = r#"<?php
+$url = env('DATABASE_URL');
+"#;
+        let usages = scan(Box::new(PhpScanner::new()), "config/db.php", content);
+        assert_eq!(names(&usages), vec!["DATABASE_URL"]);
+    }
+
+    #[test]
+    fn test_unparsed_files_fall_back() {
+        let content = "<script>const a = import.meta.env.VITE_A;</script>";
+        let usages = scan(
+            Box::new(crate::languages::ContainerScanner::new()),
+            "App.vue",
+            content,
+        );
+        assert_eq!(names(&usages), vec!["VITE_A"]);
+    }
+}
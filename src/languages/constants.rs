@@ -0,0 +1,357 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::imports::{file_imports, module_id, Import};
+use super::wrapper::dynamic_access;
+use super::ScanContext;
+use crate::types::{EnvVarUsage, Language};
+
+// String constants whose value looks like a var name, per language. Each
+// pattern has `name` and `value` groups.
+static JS_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![
+        // const KEY = "STRIPE_KEY"
+        Regex::new(
+            r#"(?m)^\s*(?:export\s+)?(?:const|let|var)\s+(?P<name>[A-Za-z_$][\w$]*)\s*(?::\s*[\w.]+\s*)?=\s*['"`](?P<value>[A-Za-z_][A-Za-z0-9_]*)['"`]"#,
+        )
+        .unwrap(),
+        // { STRIPE: "STRIPE_KEY" } object entries and enum members
+        Regex::new(
+            r#"(?m)^\s*['"]?(?P<name>[A-Za-z_$][\w$]*)['"]?\s*[:=]\s*['"](?P<value>[A-Za-z_][A-Za-z0-9_]*)['"]\s*,?\s*$"#,
+        )
+        .unwrap(),
+    ]
+});
+
+static PY_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"(?m)^\s*(?P<name>[A-Za-z_]\w*)\s*(?::\s*[\w\[\].]+\s*)?=\s*['"](?P<value>[A-Za-z_][A-Za-z0-9_]*)['"]\s*(?:#.*)?$"#,
+    )
+    .unwrap()]
+});
+
+static GO_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"(?m)^\s*(?:const\s+|var\s+)?(?P<name>[A-Za-z_]\w*)\s*(?:string\s*)?:?=\s*["`](?P<value>[A-Za-z_][A-Za-z0-9_]*)["`]"#,
+    )
+    .unwrap()]
+});
+
+static RUBY_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"(?m)^\s*(?P<name>[A-Za-z_]\w*)\s*=\s*['"](?P<value>[A-Za-z_][A-Za-z0-9_]*)['"](?:\.freeze)?\s*$"#,
+    )
+    .unwrap()]
+});
+
+static PHP_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"(?:\bconst\s+|\$)(?P<name>[A-Za-z_]\w*)\s*=\s*['"](?P<value>[A-Za-z_][A-Za-z0-9_]*)['"]"#,
+    )
+    .unwrap()]
+});
+
+static RUST_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"\b(?:const|static|let)\s+(?:mut\s+)?(?P<name>[A-Za-z_]\w*)\s*(?::\s*&(?:'static\s+)?str\s*)?=\s*"(?P<value>[A-Za-z_][A-Za-z0-9_]*)""#,
+    )
+    .unwrap()]
+});
+
+static JAVA_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"\bString\s+(?P<name>[A-Za-z_]\w*)\s*=\s*"(?P<value>[A-Za-z_][A-Za-z0-9_]*)""#,
+    )
+    .unwrap()]
+});
+
+static CSHARP_CONSTANTS: Lazy<Vec<Regex>> = Lazy::new(|| {
+    vec![Regex::new(
+        r#"\b(?:string|var)\s+(?P<name>[A-Za-z_]\w*)\s*=\s*"(?P<value>[A-Za-z_][A-Za-z0-9_]*)""#,
+    )
+    .unwrap()]
+});
+
+static KEY_EXPR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^\$?[A-Za-z_$][\w$]*(?:(?:\.|::)\$?[A-Za-z_$][\w$]*)*$").unwrap());
+
+fn patterns(language: Language) -> Option<&'static [Regex]> {
+    Some(match language {
+        Language::JavaScript | Language::TypeScript => &JS_CONSTANTS,
+        Language::Python => &PY_CONSTANTS,
+        Language::Go => &GO_CONSTANTS,
+        Language::Ruby => &RUBY_CONSTANTS,
+        Language::Php => &PHP_CONSTANTS,
+        Language::Rust => &RUST_CONSTANTS,
+        Language::Java => &JAVA_CONSTANTS,
+        Language::CSharp => &CSHARP_CONSTANTS,
+        Language::Custom(_) => return None,
+    })
+}
+
+/// String constants defined in a file, name -> value
+pub fn file_constants(language: Language, content: &str) -> HashMap<String, String> {
+    let mut constants = HashMap::new();
+    for pattern in patterns(language).unwrap_or_default() {
+        for cap in pattern.captures_iter(content) {
+            constants
+                .entry(cap["name"].to_string())
+                .or_insert_with(|| cap["value"].to_string());
+        }
+    }
+    constants
+}
+
+/// Constants a file sees from the configured key modules: those of modules
+/// it imports, or of its own package in Go, Java and C#. C# and Ruby reach
+/// other files without importing them, so a file of theirs importing no key
+/// module sees every one. A name holding different vars in different
+/// modules is ambiguous and left out.
+pub(super) fn key_constants(
+    language: Language,
+    file_path: &Path,
+    imports: &[Import],
+    context: &ScanContext,
+) -> HashMap<String, String> {
+    let same_package = matches!(language, Language::Go | Language::Java | Language::CSharp);
+    let mut modules: Vec<&HashMap<String, String>> = context
+        .key_constants
+        .iter()
+        .filter(|(module, _)| {
+            let id = module_id(module, language);
+            imports.iter().any(|i| i.names(&id))
+                || (same_package && module.parent() == file_path.parent())
+        })
+        .map(|(_, constants)| constants)
+        .collect();
+    if modules.is_empty() && matches!(language, Language::CSharp | Language::Ruby) {
+        modules = context.key_constants.values().collect();
+    }
+
+    let mut constants: HashMap<String, String> = HashMap::new();
+    let mut ambiguous = Vec::new();
+    for (name, value) in modules.into_iter().flatten() {
+        match constants.get(name) {
+            Some(seen) if seen != value => ambiguous.push(name.clone()),
+            Some(_) => {}
+            None => {
+                constants.insert(name.clone(), value.clone());
+            }
+        }
+    }
+    for name in ambiguous {
+        constants.remove(&name);
+    }
+    constants
+}
+
+/// Key-module constants visible from a file, as `key_constants`, parsing
+/// its imports only when there are key modules
+pub(super) fn file_key_constants(
+    language: Language,
+    content: &str,
+    file_path: &Path,
+    context: &ScanContext,
+) -> HashMap<String, String> {
+    if context.key_constants.is_empty() {
+        return HashMap::new();
+    }
+    let imports = file_imports(language, content, file_path);
+    key_constants(language, file_path, &imports, context)
+}
+
+/// Resolve a key expression such as `KEY`, `Keys.STRIPE` or `self::KEY` to
+/// the var name held by the constant it refers to. Unqualified names are
+/// looked up in the file's own constants first, then in the key-module
+/// constants it sees.
+pub fn resolve_key(
+    expr: &str,
+    local: &HashMap<String, String>,
+    keys: &HashMap<String, String>,
+) -> Option<String> {
+    if !KEY_EXPR.is_match(expr) {
+        return None;
+    }
+    let expr = expr.trim_start_matches('$');
+    let Some((qualifier, name)) = expr.rsplit_once(['.', ':']) else {
+        return local.get(expr).or_else(|| keys.get(expr)).cloned();
+    };
+    let name = name.trim_start_matches('$');
+
+    // Members of this class or of a class-like name (`Keys.STRIPE`) may be
+    // local; anything else (`config.name`) must come from a key module
+    let owner = qualifier.trim_end_matches(':').split(['.', ':']).next()?;
+    let class_like = matches!(owner, "self" | "this" | "static" | "cls")
+        || owner.starts_with(|c: char| c.is_ascii_uppercase());
+    class_like
+        .then(|| local.get(name))
+        .flatten()
+        .or_else(|| keys.get(name))
+        .cloned()
+}
+
+/// Report env reads keyed by a constant, e.g. `process.env[KEY]` where
+/// `const KEY = "STRIPE_KEY"`
+pub fn scan_constant_keys(
+    language: Language,
+    content: &str,
+    file_path: &Path,
+    context: &ScanContext,
+) -> Vec<EnvVarUsage> {
+    let Some(access) = dynamic_access(language) else {
+        return Vec::new();
+    };
+    let local = file_constants(language, content);
+    let keys = file_key_constants(language, content, file_path, context);
+    if local.is_empty() && keys.is_empty() {
+        return Vec::new();
+    }
+
+    let mut usages = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        for cap in access.captures_iter(line) {
+            let key = cap.get(1).unwrap();
+            if let Some(name) = resolve_key(key.as_str(), &local, &keys) {
+                usages.push(EnvVarUsage {
+                    name,
+                    file_path: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    column: key.start() + 1,
+                    language,
+                    context: Some(line.trim().to_string()),
+                    default_value: None,
+                    required: None,
+                    exposure: None,
+                });
+            }
+        }
+    }
+
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(language: Language, content: &str) -> Vec<String> {
+        scan_constant_keys(
+            language,
+            content,
+            Path::new("test"),
+            &ScanContext::default(),
+        )
+        .into_iter()
+        .map(|u| u.name)
+        .collect()
+    }
+
+    #[test]
+    fn test_javascript_constant() {
+        let content = r#"const KEY = "STRIPE_KEY";
+const stripe = process.env[KEY];"#;
+        assert_eq!(scan(Language::JavaScript, content), vec!["STRIPE_KEY"]);
+    }
+
+    #[test]
+    fn test_python_class_constant() {
+        let content = r#"class Keys:
+    SENTRY = "SENTRY_DSN"
+
+dsn = os.environ[Keys.SENTRY]
+"#;
+        assert_eq!(scan(Language::Python, content), vec!["SENTRY_DSN"]);
+    }
+
+    #[test]
+    fn test_java_static_final() {
+        let content = r#"private static final String DB = "DATABASE_URL";
+String url = System.getenv(DB);"#;
+        assert_eq!(scan(Language::Java, content), vec!["DATABASE_URL"]);
+    }
+
+    #[test]
+    fn test_php_class_constant() {
+        let content = r#"const API_KEY = 'MAILGUN_KEY';
+$key = getenv(self::API_KEY);"#;
+        assert_eq!(scan(Language::Php, content), vec!["MAILGUN_KEY"]);
+    }
+
+    #[test]
+    fn test_key_module() {
+        let mut context = ScanContext::default();
+        context.key_constants.insert(
+            "internal/keys/keys.go".into(),
+            file_constants(Language::Go, "const (\n\tRedisURL = \"REDIS_URL\"\n)\n"),
+        );
+
+        let content =
+            "import \"example.com/app/internal/keys\"\n\naddr := os.Getenv(keys.RedisURL)";
+        let usages = scan_constant_keys(Language::Go, content, Path::new("main.go"), &context);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "REDIS_URL");
+        assert_eq!((usages[0].line, usages[0].column), (3, 19));
+
+        // Not imported, and not in the same package
+        let usages = scan_constant_keys(
+            Language::Go,
+            "addr := os.Getenv(keys.RedisURL)",
+            Path::new("main.go"),
+            &context,
+        );
+        assert!(usages.is_empty());
+    }
+
+    #[test]
+    fn test_key_modules_in_scope() {
+        let mut context = ScanContext::default();
+        context.key_constants.insert(
+            "web/src/keys.ts".into(),
+            file_constants(
+                Language::TypeScript,
+                "export const DB = \"WEB_DATABASE_URL\";\n",
+            ),
+        );
+        context.key_constants.insert(
+            "api/src/keys.ts".into(),
+            file_constants(
+                Language::TypeScript,
+                "export const DB = \"API_DATABASE_URL\";\nexport const PORT = \"PORT\";\n",
+            ),
+        );
+        let names = |file: &str, content: &str| -> Vec<String> {
+            scan_constant_keys(Language::TypeScript, content, Path::new(file), &context)
+                .into_iter()
+                .map(|u| u.name)
+                .collect()
+        };
+
+        let read = "import { DB } from './keys';\nconst url = process.env[DB];\n";
+        assert_eq!(names("web/src/db.ts", read), vec!["WEB_DATABASE_URL"]);
+        assert_eq!(names("api/src/db.ts", read), vec!["API_DATABASE_URL"]);
+        assert!(names("scripts/db.ts", "const url = process.env[DB];\n").is_empty());
+
+        // Ruby sees every key module, but not names they disagree on
+        context.key_constants.insert(
+            "config/keys.rb".into(),
+            file_constants(Language::Ruby, "DB = 'RUBY_DATABASE_URL'\nPORT = 'PORT'\n"),
+        );
+        let ruby = |content: &str| -> Vec<String> {
+            scan_constant_keys(Language::Ruby, content, Path::new("app/db.rb"), &context)
+                .into_iter()
+                .map(|u| u.name)
+                .collect()
+        };
+        assert!(ruby("url = ENV[Keys::DB]\n").is_empty());
+        assert_eq!(ruby("port = ENV[Keys::PORT]\n"), vec!["PORT"]);
+    }
+
+    #[test]
+    fn test_unrelated_member_not_resolved() {
+        let content = r#"const name = "John";
+const v = process.env[user.name];"#;
+        assert!(scan(Language::JavaScript, content).is_empty());
+    }
+}
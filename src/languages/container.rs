@@ -0,0 +1,186 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use super::javascript::JavaScriptScanner;
+use super::{LanguageScanner, ScanContext};
+use crate::types::{EnvVarUsage, Language};
+
+/// Scanner for component and page files that embed JavaScript: Vue, Svelte,
+/// Astro and HTML. Script regions are extracted and handed to
+/// `JavaScriptScanner`, with positions mapped back to the container file.
+pub struct ContainerScanner {
+    inner: JavaScriptScanner,
+}
+
+// <script>, <script setup lang="ts">, <script context="module">, ...
+static SCRIPT_BLOCK: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?is)<script\b([^>]*)>(.*?)</script\s*>"#).unwrap());
+
+// Script types that hold data rather than code
+static NON_JS_TYPE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)\btype\s*=\s*["']?[^"'\s>]*(?:json|template)"#).unwrap());
+
+// Astro component frontmatter between leading `---` fences
+static ASTRO_FRONTMATTER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?s)\A\s*---[ \t]*\r?\n(.*?)\r?\n---"#).unwrap());
+
+/// A slice of the container holding script code
+struct ScriptRegion<'a> {
+    text: &'a str,
+    /// 1-based line of the region's first character
+    line: usize,
+    /// 0-based column of the region's first character
+    column: usize,
+}
+
+impl ContainerScanner {
+    pub fn new() -> Self {
+        Self {
+            inner: JavaScriptScanner::new(),
+        }
+    }
+
+    fn regions<'a>(content: &'a str, file_path: &Path) -> Vec<ScriptRegion<'a>> {
+        let mut regions = Vec::new();
+
+        let position = |offset: usize| {
+            let before = &content[..offset];
+            let line = before.matches('\n').count() + 1;
+            let column = offset - before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+            (line, column)
+        };
+
+        let is_astro = file_path.extension().and_then(|e| e.to_str()) == Some("astro");
+        if is_astro {
+            if let Some(m) = ASTRO_FRONTMATTER.captures(content).and_then(|c| c.get(1)) {
+                let (line, column) = position(m.start());
+                regions.push(ScriptRegion {
+                    text: m.as_str(),
+                    line,
+                    column,
+                });
+            }
+        }
+
+        for cap in SCRIPT_BLOCK.captures_iter(content) {
+            if NON_JS_TYPE.is_match(&cap[1]) {
+                continue;
+            }
+            let m = cap.get(2).unwrap();
+            let (line, column) = position(m.start());
+            regions.push(ScriptRegion {
+                text: m.as_str(),
+                line,
+                column,
+            });
+        }
+
+        regions
+    }
+}
+
+impl Default for ContainerScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageScanner for ContainerScanner {
+    fn language(&self) -> Language {
+        Language::JavaScript
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["vue", "svelte", "astro", "html", "htm"]
+    }
+
+    fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        self.scan_with_context(content, file_path, &ScanContext::default())
+    }
+
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
+        let mut usages = Vec::new();
+
+        for region in Self::regions(content, file_path) {
+            for mut usage in self
+                .inner
+                .scan_with_context(region.text, file_path, context)
+            {
+                if usage.line == 1 {
+                    usage.column += region.column;
+                }
+                usage.line += region.line - 1;
+                usages.push(usage);
+            }
+        }
+
+        usages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vue_script_setup() {
+        let scanner = ContainerScanner::new();
+        let content = r#"<template>
+  <div>{{ title }}</div>
+</template>
+
+<script setup lang="ts">
+const api = import.meta.env.VITE_API_URL;
+</script>
+"#;
+        let usages = scanner.scan(content, Path::new("App.vue"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "VITE_API_URL");
+        assert_eq!(usages[0].line, 6);
+        assert_eq!(usages[0].column, 29);
+    }
+
+    #[test]
+    fn test_svelte_module_script() {
+        let scanner = ContainerScanner::new();
+        let content = r#"<script context="module">export const key = import.meta.env.VITE_KEY;</script>
+<script>
+  let debug = import.meta.env.VITE_DEBUG;
+</script>"#;
+        let usages = scanner.scan(content, Path::new("Page.svelte"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "VITE_KEY");
+        assert_eq!((usages[0].line, usages[0].column), (1, 61));
+        assert_eq!((usages[1].line, usages[1].column), (3, 31));
+    }
+
+    #[test]
+    fn test_astro_frontmatter() {
+        let scanner = ContainerScanner::new();
+        let content = r#"---
+const site = import.meta.env.PUBLIC_SITE_URL;
+---
+<h1>{site}</h1>
+"#;
+        let usages = scanner.scan(content, Path::new("index.astro"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "PUBLIC_SITE_URL");
+        assert_eq!(usages[0].line, 2);
+    }
+
+    #[test]
+    fn test_html_skips_json_scripts() {
+        let scanner = ContainerScanner::new();
+        let content = r#"<script type="application/json">{"a": "process.env.NOPE"}</script>
+<script type="module">console.log(import.meta.env.VITE_MODE)</script>"#;
+        let usages = scanner.scan(content, Path::new("index.html"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "VITE_MODE");
+    }
+}
@@ -0,0 +1,106 @@
+use once_cell::sync::OnceCell;
+use regex::Regex;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
+
+use super::wrapper::wrapper_call_pattern;
+use super::EnvWrapper;
+
+/// Project-wide facts collected from every file before scanning, for env
+/// mappings whose pieces live in different files
+#[derive(Debug, Default)]
+pub struct ScanContext {
+    /// Prefix passed to .NET `AddEnvironmentVariables("APP_")`, keyed by the
+    /// directory of the file calling it
+    pub dotnet_env_prefixes: HashMap<PathBuf, String>,
+
+    /// .NET configuration keys read in code, as env names without a prefix;
+    /// sections read as a whole end with `__`
+    pub dotnet_read_keys: HashSet<String>,
+
+    /// .NET options class name -> settable property names
+    pub dotnet_options: HashMap<String, Vec<String>>,
+
+    /// Go struct name -> its fields whose type may be another struct, so
+    /// env prefixes compose through structs defined in other files
+    pub go_struct_fields: HashMap<String, Vec<GoNestedField>>,
+
+    /// Go struct name -> prefixes it is passed to `envconfig.Process` with
+    pub go_envconfig_prefixes: HashMap<String, BTreeSet<String>>,
+
+    /// Laravel config key (`database.connections.pgsql.url`) -> env var
+    /// feeding it, keyed by the app root holding the `config/` directory
+    pub php_config_env: HashMap<PathBuf, HashMap<String, String>>,
+
+    /// Functions that read the env var named by one of their arguments
+    pub env_wrappers: Vec<EnvWrapper>,
+
+    /// String constants of each configured key module, name -> var name
+    pub key_constants: HashMap<PathBuf, HashMap<String, String>>,
+
+    /// Calls to any of `env_wrappers`, built on first use
+    wrapper_calls: OnceCell<Option<Regex>>,
+}
+
+/// A Go struct field holding a named type, which may be a struct
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoNestedField {
+    /// Type name without package qualifier or pointer
+    pub ty: String,
+    /// caarlos0/env `envPrefix` tag
+    pub env_prefix: Option<String>,
+    /// envconfig key of the field: its `envconfig` tag or its upper-cased name
+    pub envconfig_key: String,
+}
+
+impl ScanContext {
+    /// Combine facts collected from different files
+    pub fn merge(mut self, other: ScanContext) -> Self {
+        for (dir, prefix) in other.dotnet_env_prefixes {
+            // Smallest wins, whichever file was merged first
+            self.dotnet_env_prefixes
+                .entry(dir)
+                .and_modify(|p| {
+                    if prefix < *p {
+                        *p = prefix.clone();
+                    }
+                })
+                .or_insert(prefix);
+        }
+        self.dotnet_read_keys.extend(other.dotnet_read_keys);
+        for (class, props) in other.dotnet_options {
+            self.dotnet_options.entry(class).or_default().extend(props);
+        }
+        for (name, fields) in other.go_struct_fields {
+            self.go_struct_fields
+                .entry(name)
+                .or_default()
+                .extend(fields);
+        }
+        for (name, prefixes) in other.go_envconfig_prefixes {
+            self.go_envconfig_prefixes
+                .entry(name)
+                .or_default()
+                .extend(prefixes);
+        }
+        for (root, keys) in other.php_config_env {
+            self.php_config_env.entry(root).or_default().extend(keys);
+        }
+        self.env_wrappers.extend(other.env_wrappers);
+        for (module, constants) in other.key_constants {
+            self.key_constants
+                .entry(module)
+                .or_default()
+                .extend(constants);
+        }
+        self
+    }
+
+    /// Regex matching calls to the known wrappers, None when there are none.
+    /// Built once, so add every wrapper before scanning.
+    pub fn wrapper_calls(&self) -> Option<&Regex> {
+        self.wrapper_calls
+            .get_or_init(|| wrapper_call_pattern(&self.env_wrappers))
+            .as_ref()
+    }
+}
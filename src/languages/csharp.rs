@@ -1,25 +1,168 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{LanguageScanner, ScanContext};
 use crate::types::{EnvVarUsage, Language};
 
-/// Scanner for C# files
+/// Scanner for C# files and `appsettings*.json`
+///
+/// .NET configuration keys such as `Smtp:Host` are overridden from the
+/// environment by replacing `:` with `__`. .NET matches them ignoring case,
+/// so they are reported upper-cased as `SMTP__HOST`, prefixed when the
+/// project calls `AddEnvironmentVariables("APP_")`.
 pub struct CSharpScanner;
 
 static ENVIRONMENT_GETENV: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"Environment\.GetEnvironmentVariable\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap()
+    Regex::new(r#"Environment\.GetEnvironmentVariable\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap()
 });
 
-static CONFIG_MANAGER: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"ConfigurationManager\.AppSettings\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap()
+// configuration["Section:Key"]; `config` must be typed IConfiguration in the file
+static CONFIG_INDEXER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(_?[Cc]onfig(?:uration)?)\s*\[\s*"([^"]+)"\s*\]"#).unwrap());
+
+// configuration.GetValue<int>("Section:Key", 8080)
+static CONFIG_GET_VALUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\.GetValue\s*<[^>]+>\s*\(\s*"([^"]+)"(?:\s*,\s*"?([^")]*)"?)?"#).unwrap()
+});
+
+static CONFIG_CONNECTION_STRING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\.GetConnectionString\s*\(\s*"([^"]+)""#).unwrap());
+
+// services.Configure<SmtpOptions>(config.GetSection("Smtp"))
+static CONFIGURE_SECTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"Configure\s*<\s*(\w+)\s*>\s*\([^)]*GetSection\s*\(\s*"([^"]+)""#).unwrap()
+});
+
+// config.GetSection("Smtp").Get<SmtpOptions>()
+static SECTION_GET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"GetSection\s*\(\s*"([^"]+)"\s*\)\s*\.\s*Get\s*<\s*(\w+)\s*>"#).unwrap()
+});
+
+// Any section read, for matching appsettings keys
+static GET_SECTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"GetSection\s*\(\s*"([^"]+)""#).unwrap());
+
+// config.GetSection("Smtp").Bind(options)
+static SECTION_BIND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"GetSection\s*\(\s*"([^"]+)"\s*\)\s*\.\s*Bind\s*\(\s*(\w+)"#).unwrap()
+});
+
+// var options = new SmtpOptions(); / SmtpOptions options = new();
+static NEW_INSTANCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bvar\s+(\w+)\s*=\s*new\s+(\w+)|\b(\w+)\s+(\w+)\s*=\s*new\s*\("#).unwrap()
+});
+
+static ADD_ENV_VARS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"AddEnvironmentVariables\s*\(\s*(?:prefix\s*:\s*)?"([^"]*)""#).unwrap()
 });
 
+static CLASS_DEF: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\b(?:class|record)\s+(\w+)"#).unwrap());
+
+static SETTABLE_PROPERTY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*public\s+[\w<>\[\]?,\s]+?\s+(\w+)\s*\{\s*get;\s*(?:set|init);"#).unwrap()
+});
+
+// "Key": ... in appsettings.json
+static JSON_KEY: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]+)"\s*:\s*(.*)$"#).unwrap());
+
 impl CSharpScanner {
     pub fn new() -> Self {
         Self
     }
+
+    fn is_appsettings(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| n.starts_with("appsettings") && n.ends_with(".json"))
+            .unwrap_or(false)
+    }
+
+    /// Env var name that overrides a configuration key, without a prefix
+    fn key_env_name(key: &str) -> String {
+        key.replace(':', "__").to_uppercase()
+    }
+
+    /// Env var name that overrides a configuration key read in `file_path`,
+    /// with the prefix of the nearest enclosing project
+    fn env_name(key: &str, file_path: &Path, context: &ScanContext) -> String {
+        let prefix = context
+            .dotnet_env_prefixes
+            .iter()
+            .filter(|(dir, _)| file_path.starts_with(dir))
+            .max_by_key(|(dir, _)| dir.components().count())
+            .map_or("", |(_, prefix)| prefix.as_str());
+        format!("{}{}", prefix.to_uppercase(), Self::key_env_name(key))
+    }
+
+    /// Configuration keys read through indexers, with their match
+    fn indexer_keys<'a>(content: &str, line: &'a str) -> Vec<regex::Match<'a>> {
+        CONFIG_INDEXER
+            .captures_iter(line)
+            .filter(|cap| cap[1].ends_with("onfiguration") || content.contains("IConfiguration"))
+            .map(|cap| cap.get(2).unwrap())
+            .collect()
+    }
+
+    /// Leaf keys of an appsettings file that code reads, flattened to
+    /// `Section:Key` with their values as defaults
+    fn scan_appsettings(
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
+        let mut usages = Vec::new();
+        // Section path, with None marking arrays whose elements are skipped
+        let mut stack: Vec<Option<String>> = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if let Some(cap) = JSON_KEY.captures(trimmed) {
+                let key = &cap[1];
+                let value = cap[2].trim().trim_end_matches(',').trim();
+
+                if value.starts_with('{') && !value.ends_with('}') {
+                    stack.push(Some(key.to_string()));
+                } else if value.starts_with('[') && !value.ends_with(']') {
+                    stack.push(None);
+                } else if stack.iter().all(Option::is_some) {
+                    let mut path: Vec<&str> = stack.iter().flatten().map(String::as_str).collect();
+                    path.push(key);
+                    let key_path = path.join(":");
+                    let read = Self::key_env_name(&key_path);
+                    if !context
+                        .dotnet_read_keys
+                        .iter()
+                        .any(|k| *k == read || (k.ends_with("__") && read.starts_with(k.as_str())))
+                    {
+                        continue;
+                    }
+                    let column = line.find(key).unwrap_or(0) + 1;
+
+                    usages.push(EnvVarUsage {
+                        name: Self::env_name(&key_path, file_path, context),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num + 1,
+                        column,
+                        language: Language::CSharp,
+                        context: Some(trimmed.to_string()),
+                        default_value: Some(value.trim_matches('"').to_string()),
+                        required: Some(false),
+                        exposure: None,
+                    });
+                }
+            } else if trimmed.starts_with('{') && !stack.is_empty() && !trimmed.ends_with('}') {
+                // Object inside an array
+                stack.push(None);
+            } else if (trimmed.starts_with('}') || trimmed.starts_with(']')) && !stack.is_empty() {
+                stack.pop();
+            }
+        }
+
+        usages
+    }
 }
 
 impl Default for CSharpScanner {
@@ -34,28 +177,171 @@ impl LanguageScanner for CSharpScanner {
     }
 
     fn extensions(&self) -> &'static [&'static str] {
-        &["cs"]
+        &["cs", "json"]
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("cs") => true,
+            Some(_) => Self::is_appsettings(path),
+            None => false,
+        }
     }
 
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        self.scan_with_context(content, file_path, &ScanContext::default())
+    }
+
+    fn collect_context(&self, content: &str, file_path: &Path, context: &mut ScanContext) {
+        if Self::is_appsettings(file_path) {
+            return;
+        }
+
+        if let Some(cap) = ADD_ENV_VARS.captures(content) {
+            let dir = file_path.parent().unwrap_or(Path::new("")).to_path_buf();
+            context.dotnet_env_prefixes.insert(dir, cap[1].to_string());
+        }
+
+        // Keys read in code, so appsettings only reports those
+        for line in content.lines() {
+            let keys = Self::indexer_keys(content, line)
+                .into_iter()
+                .map(|m| m.as_str().to_string())
+                .chain(
+                    CONFIG_GET_VALUE
+                        .captures_iter(line)
+                        .map(|c| c[1].to_string()),
+                )
+                .chain(
+                    CONFIG_CONNECTION_STRING
+                        .captures_iter(line)
+                        .map(|c| format!("ConnectionStrings:{}", &c[1])),
+                );
+            for key in keys {
+                context.dotnet_read_keys.insert(Self::key_env_name(&key));
+            }
+            for cap in GET_SECTION.captures_iter(line) {
+                let section = format!("{}__", Self::key_env_name(&cap[1]));
+                context.dotnet_read_keys.insert(section);
+            }
+        }
+
+        // Settable properties of every class, for options binding
+        let mut current: Option<String> = None;
+        for line in content.lines() {
+            if let Some(cap) = CLASS_DEF.captures(line) {
+                current = Some(cap[1].to_string());
+                continue;
+            }
+            if let (Some(class), Some(cap)) = (&current, SETTABLE_PROPERTY.captures(line)) {
+                context
+                    .dotnet_options
+                    .entry(class.clone())
+                    .or_default()
+                    .push(cap[1].to_string());
+            }
+        }
+    }
+
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
+        if Self::is_appsettings(file_path) {
+            return Self::scan_appsettings(content, file_path, context);
+        }
+
         let mut usages = Vec::new();
-        let patterns: Vec<&Lazy<Regex>> = vec![&ENVIRONMENT_GETENV, &CONFIG_MANAGER];
+
+        // Variable name -> type, to resolve Bind(options) targets
+        let mut var_types: HashMap<&str, &str> = HashMap::new();
+        for cap in NEW_INSTANCE.captures_iter(content) {
+            if let (Some(var), Some(ty)) = (cap.get(1).or(cap.get(4)), cap.get(2).or(cap.get(3))) {
+                var_types.insert(var.as_str(), ty.as_str());
+            }
+        }
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
+            let mut push = |name: String, column: usize, default_value: Option<String>| {
+                usages.push(EnvVarUsage {
+                    name,
+                    file_path: file_path.to_path_buf(),
+                    line: line_num,
+                    column,
+                    language: Language::CSharp,
+                    context: Some(line.trim().to_string()),
+                    default_value,
+                    required: None,
+                    exposure: None,
+                });
+            };
 
-            for pattern in &patterns {
-                for cap in pattern.captures_iter(line) {
-                    if let Some(m) = cap.get(1) {
-                        usages.push(EnvVarUsage {
-                            name: m.as_str().to_string(),
-                            file_path: file_path.to_path_buf(),
-                            line: line_num,
-                            column: m.start() + 1,
-                            language: Language::CSharp,
-                            context: Some(line.trim().to_string()),
-                        });
-                    }
+            for cap in ENVIRONMENT_GETENV.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                push(m.as_str().to_string(), m.start() + 1, None);
+            }
+
+            for m in Self::indexer_keys(content, line) {
+                push(
+                    Self::env_name(m.as_str(), file_path, context),
+                    m.start() + 1,
+                    None,
+                );
+            }
+
+            for cap in CONFIG_GET_VALUE.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                let default_value = cap.get(2).map(|d| d.as_str().trim().to_string());
+                push(
+                    Self::env_name(m.as_str(), file_path, context),
+                    m.start() + 1,
+                    default_value,
+                );
+            }
+
+            for cap in CONFIG_CONNECTION_STRING.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                let key = format!("ConnectionStrings:{}", m.as_str());
+                push(
+                    Self::env_name(&key, file_path, context),
+                    m.start() + 1,
+                    None,
+                );
+            }
+
+            // Options classes bound to a section, one var per property
+            let mut bindings: Vec<(usize, &str, &str)> = Vec::new();
+            for cap in CONFIGURE_SECTION.captures_iter(line) {
+                let section = cap.get(2).unwrap();
+                bindings.push((
+                    section.start(),
+                    section.as_str(),
+                    cap.get(1).unwrap().as_str(),
+                ));
+            }
+            for cap in SECTION_GET.captures_iter(line) {
+                let section = cap.get(1).unwrap();
+                bindings.push((
+                    section.start(),
+                    section.as_str(),
+                    cap.get(2).unwrap().as_str(),
+                ));
+            }
+            for cap in SECTION_BIND.captures_iter(line) {
+                let section = cap.get(1).unwrap();
+                let target = cap.get(2).unwrap().as_str();
+                if let Some(ty) = var_types.get(target) {
+                    bindings.push((section.start(), section.as_str(), ty));
+                }
+            }
+
+            for (start, section, class) in bindings {
+                for prop in context.dotnet_options.get(class).into_iter().flatten() {
+                    let key = format!("{}:{}", section, prop);
+                    push(Self::env_name(&key, file_path, context), start + 1, None);
                 }
             }
         }
@@ -78,11 +364,115 @@ mod tests {
     }
 
     #[test]
-    fn test_config_manager() {
+    fn test_config_manager_is_not_env() {
         let scanner = CSharpScanner::new();
         let content = r#"var apiKey = ConfigurationManager.AppSettings["API_KEY"];"#;
         let usages = scanner.scan(content, Path::new("Config.cs"));
+        assert!(usages.is_empty());
+    }
+
+    #[test]
+    fn test_configuration_indexer() {
+        let scanner = CSharpScanner::new();
+        let content = r#"var host = builder.Configuration["Smtp:Host"];"#;
+        let usages = scanner.scan(content, Path::new("Program.cs"));
         assert_eq!(usages.len(), 1);
-        assert_eq!(usages[0].name, "API_KEY");
+        assert_eq!(usages[0].name, "SMTP__HOST");
+
+        // Dictionaries that happen to be called config
+        let content = r#"var configDict = new Dictionary<string, string>();
+var a = configDict["Smtp:Host"];
+var b = config["Smtp:Host"];"#;
+        assert!(scanner.scan(content, Path::new("Cache.cs")).is_empty());
+
+        let content = r#"public Mailer(IConfiguration config) { _host = config["Smtp:Host"]; }"#;
+        let usages = scanner.scan(content, Path::new("Mailer.cs"));
+        assert_eq!(usages.len(), 1);
+    }
+
+    #[test]
+    fn test_get_value_and_connection_string() {
+        let scanner = CSharpScanner::new();
+        let content = r#"
+var port = config.GetValue<int>("Server:Port", 8080);
+var conn = config.GetConnectionString("Default");
+"#;
+        let usages = scanner.scan(content, Path::new("Program.cs"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "SERVER__PORT");
+        assert_eq!(usages[0].default_value.as_deref(), Some("8080"));
+        assert_eq!(usages[1].name, "CONNECTIONSTRINGS__DEFAULT");
+    }
+
+    #[test]
+    fn test_section_binding_with_prefix() {
+        let scanner = CSharpScanner::new();
+        let options = r#"
+public class SmtpOptions
+{
+    public string Host { get; set; }
+    public int Port { get; init; }
+}
+"#;
+        let program = r#"
+builder.Configuration.AddEnvironmentVariables(prefix: "APP_");
+builder.Services.Configure<SmtpOptions>(builder.Configuration.GetSection("Smtp"));
+"#;
+        let mut context = ScanContext::default();
+        scanner.collect_context(options, Path::new("SmtpOptions.cs"), &mut context);
+        scanner.collect_context(program, Path::new("Program.cs"), &mut context);
+
+        let usages = scanner.scan_with_context(program, Path::new("Program.cs"), &context);
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["APP_SMTP__HOST", "APP_SMTP__PORT"]);
+    }
+
+    #[test]
+    fn test_prefix_per_project() {
+        let scanner = CSharpScanner::new();
+        let api = r#"builder.Configuration.AddEnvironmentVariables("API_");"#;
+        let worker = r#"builder.Configuration.AddEnvironmentVariables("WORKER_");"#;
+        let mut context = ScanContext::default();
+        scanner.collect_context(api, Path::new("src/Api/Program.cs"), &mut context);
+        scanner.collect_context(worker, Path::new("src/Worker/Program.cs"), &mut context);
+
+        let read = r#"var host = builder.Configuration["Smtp:Host"];"#;
+        let usages =
+            scanner.scan_with_context(read, Path::new("src/Worker/Jobs/Mail.cs"), &context);
+        assert_eq!(usages[0].name, "WORKER_SMTP__HOST");
+        let usages = scanner.scan_with_context(read, Path::new("src/Api/Mail.cs"), &context);
+        assert_eq!(usages[0].name, "API_SMTP__HOST");
+    }
+
+    #[test]
+    fn test_appsettings_json() {
+        let scanner = CSharpScanner::new();
+        let content = r#"{
+  "Logging": {
+    "LogLevel": {
+      "Default": "Information"
+    }
+  },
+  "AllowedHosts": "*",
+  "Endpoints": [
+    { "Url": "http://a" }
+  ],
+  "FeatureFlags": {
+    "Beta": "false"
+  }
+}"#;
+        let program = r#"
+var hosts = builder.Configuration["AllowedHosts"];
+builder.Services.Configure<LoggerFilterOptions>(builder.Configuration.GetSection("Logging"));
+"#;
+        let mut context = ScanContext::default();
+        scanner.collect_context(program, Path::new("Program.cs"), &mut context);
+
+        // FeatureFlags is never read in code
+        let usages = scanner.scan_with_context(content, Path::new("appsettings.json"), &context);
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["LOGGING__LOGLEVEL__DEFAULT", "ALLOWEDHOSTS"]);
+        assert_eq!(usages[0].default_value.as_deref(), Some("Information"));
+        assert!(!scanner.matches_file(Path::new("package.json")));
     }
 }
@@ -0,0 +1,154 @@
+use regex::Regex;
+use std::path::Path;
+
+use super::LanguageScanner;
+use crate::config::CustomPattern;
+use crate::types::{EnvVarUsage, Language};
+
+/// Scanner built from a `[[scan.patterns]]` entry in the config
+pub struct PatternScanner {
+    languages: Vec<Language>,
+    regex: Regex,
+    extensions: Vec<String>,
+    write: bool,
+}
+
+impl PatternScanner {
+    /// Compile a configured pattern. Returns None if the regex is invalid or no
+    /// known language is listed.
+    pub fn from_config(pattern: &CustomPattern) -> Option<Self> {
+        let languages: Vec<Language> = pattern
+            .languages
+            .iter()
+            .filter_map(|l| Language::from_name(l))
+            .collect();
+        if languages.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            languages,
+            regex: Regex::new(&pattern.regex).ok()?,
+            extensions: pattern
+                .extensions
+                .iter()
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .collect(),
+            write: pattern.is_write(),
+        })
+    }
+
+    fn language_for(&self, ext: &str) -> Option<Language> {
+        self.languages
+            .iter()
+            .copied()
+            .find(|lang| lang.extensions().contains(&ext))
+            .or_else(|| {
+                self.extensions
+                    .iter()
+                    .any(|e| e == ext)
+                    .then_some(self.languages[0])
+            })
+    }
+}
+
+impl LanguageScanner for PatternScanner {
+    fn language(&self) -> Language {
+        self.languages[0]
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        // Extensions are configured at runtime, see matches_file
+        &[]
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(|e| self.language_for(&e.to_lowercase()))
+            .is_some()
+    }
+
+    fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        let mut usages = Vec::new();
+
+        let language = file_path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(|e| self.language_for(&e.to_lowercase()))
+            .unwrap_or(self.languages[0]);
+
+        for (line_num, line) in content.lines().enumerate() {
+            let line_num = line_num + 1;
+
+            for cap in self.regex.captures_iter(line) {
+                if let Some(m) = cap.name("name").or_else(|| cap.get(1)) {
+                    usages.push(EnvVarUsage {
+                        name: m.as_str().to_string(),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num,
+                        column: m.start() + 1,
+                        language,
+                        context: Some(line.trim().to_string()),
+                        default_value: cap.name("default").map(|d| d.as_str().to_string()),
+                        required: if self.write { Some(false) } else { None },
+                        exposure: None,
+                    });
+                }
+            }
+        }
+
+        usages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_pattern(regex: &str, extensions: Vec<&str>) -> CustomPattern {
+        CustomPattern {
+            languages: vec!["python".to_string()],
+            regex: regex.to_string(),
+            kind: "read".to_string(),
+            extensions: extensions.into_iter().map(String::from).collect(),
+        }
+    }
+
+    #[test]
+    fn test_named_capture() {
+        let pattern = make_pattern(r#"cfg\.get\("(?P<name>[A-Z_]+)""#, vec![]);
+        let scanner = PatternScanner::from_config(&pattern).unwrap();
+        let usages = scanner.scan(r#"url = cfg.get("DATABASE_URL")"#, Path::new("app.py"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "DATABASE_URL");
+        assert_eq!(usages[0].language, Language::Python);
+    }
+
+    #[test]
+    fn test_default_capture() {
+        let pattern = make_pattern(
+            r#"settings\.env\("([A-Z_]+)",\s*"(?P<default>[^"]*)"\)"#,
+            vec![],
+        );
+        let scanner = PatternScanner::from_config(&pattern).unwrap();
+        let usages = scanner.scan(r#"settings.env("PORT", "8000")"#, Path::new("app.py"));
+        assert_eq!(usages[0].name, "PORT");
+        assert_eq!(usages[0].default_value.as_deref(), Some("8000"));
+    }
+
+    #[test]
+    fn test_extra_extensions() {
+        let pattern = make_pattern(r#"getConfig\("([A-Z_]+)"\)"#, vec!["tmpl"]);
+        let scanner = PatternScanner::from_config(&pattern).unwrap();
+        assert!(scanner.matches_file(Path::new("app.py")));
+        assert!(scanner.matches_file(Path::new("page.tmpl")));
+        assert!(!scanner.matches_file(Path::new("app.rb")));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let pattern = make_pattern(r#"getConfig\("([A-Z_]+"#, vec![]);
+        assert!(PatternScanner::from_config(&pattern).is_none());
+    }
+}
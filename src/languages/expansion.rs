@@ -0,0 +1,216 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use crate::types::{EnvVarUsage, Language};
+
+// `$X` and `${X}` inside a Go or Python expanded string; `${X:-default}`
+// looks up a var literally named `X:-default` there
+static SHELL_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\$(?:\{(?P<braced>[A-Za-z_][A-Za-z0-9_]*)\}|(?P<bare>[A-Za-z_][A-Za-z0-9_]*))")
+        .unwrap()
+});
+
+// shellexpand also understands `${X:-default}`
+static SHELLEXPAND_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\$(?:\{(?P<braced>[A-Za-z_][A-Za-z0-9_]*)(?::-(?P<default>[^}]*))?\}|(?P<bare>[A-Za-z_][A-Za-z0-9_]*))",
+    )
+    .unwrap()
+});
+
+// StringSubstitutor only expands `${X}`; `${env:X}` is the interpolator's env lookup
+static SUBSTITUTOR_REFERENCE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\$\{(?P<prefix>env:)?(?P<braced>[A-Za-z_][A-Za-z0-9_]*)(?::-(?P<default>[^}]*))?\}",
+    )
+    .unwrap()
+});
+
+// os.ExpandEnv("...") and os.Expand("...", os.Getenv), with raw strings
+static GO_EXPAND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\bos\.(?P<func>ExpandEnv|Expand)\(\s*(?:"(?P<dq>(?:[^"\\]|\\.)*)"|`(?P<raw>[^`]*)`)(?P<rest>[^)]*)"#,
+    )
+    .unwrap()
+});
+
+// os.path.expandvars("...") or a bare expandvars() imported from os.path
+static PY_EXPANDVARS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?:^|[^\w.]|\bos\.path\.)expandvars\(\s*[rbu]?(?:"(?P<dq>[^"]*)"|'(?P<sq>[^']*)')"#,
+    )
+    .unwrap()
+});
+
+// shellexpand::env("...") / shellexpand::full("...")
+static RUST_SHELLEXPAND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bshellexpand::(?:env|full)\(\s*"(?P<dq>(?:[^"\\]|\\.)*)""#).unwrap()
+});
+
+// sub.replace("...") / StringSubstitutor.replace("...", System.getenv())
+static JAVA_REPLACE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?P<receiver>\w+)\.replace\(\s*"(?P<dq>(?:[^"\\]|\\.)*)"(?P<rest>[^)]*)"#)
+        .unwrap()
+});
+
+// sub = new StringSubstitutor(System.getenv())
+static JAVA_ENV_SUBSTITUTOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\w+)\s*=\s*new\s+StringSubstitutor\s*\(\s*System\.getenv\(\s*\)").unwrap()
+});
+
+// interpolator = StringSubstitutor.createInterpolator()
+static JAVA_INTERPOLATOR: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\w+)\s*=\s*StringSubstitutor\.createInterpolator\(\s*\)").unwrap()
+});
+
+/// Env vars referenced inside strings passed to expansion helpers, such as
+/// Go `os.ExpandEnv("$HOME/${APP_DIR}")` or Python `os.path.expandvars`
+pub(super) fn scan_expansions(
+    language: Language,
+    content: &str,
+    file_path: &Path,
+) -> Vec<EnvVarUsage> {
+    let (call, reference): (&Regex, &Regex) = match language {
+        Language::Go => (&GO_EXPAND, &SHELL_REFERENCE),
+        Language::Python => (&PY_EXPANDVARS, &SHELL_REFERENCE),
+        Language::Rust => (&RUST_SHELLEXPAND, &SHELLEXPAND_REFERENCE),
+        Language::Java if content.contains("StringSubstitutor") => {
+            (&JAVA_REPLACE, &SUBSTITUTOR_REFERENCE)
+        }
+        _ => return Vec::new(),
+    };
+
+    // Substitutor variables: plain ${X} only resolves from the environment
+    // when the substitutor is built over System.getenv(); an interpolator
+    // only resolves ${env:X}
+    let variables = |re: &Regex| -> Vec<String> {
+        re.captures_iter(content)
+            .map(|c| c[1].to_string())
+            .collect()
+    };
+    let env_substitutors = variables(&JAVA_ENV_SUBSTITUTOR);
+    let interpolators = variables(&JAVA_INTERPOLATOR);
+
+    let mut usages = Vec::new();
+    for (line_num, line) in content.lines().enumerate() {
+        for cap in call.captures_iter(line) {
+            let rest = cap.name("rest").map_or("", |r| r.as_str());
+            // os.Expand only reads env when its mapping is os.Getenv
+            if cap.name("func").is_some_and(|f| f.as_str() == "Expand")
+                && !rest.contains("os.Getenv")
+            {
+                continue;
+            }
+            let env_only = match cap.name("receiver").map(|r| r.as_str()) {
+                None => false,
+                Some("StringSubstitutor") if rest.contains("System.getenv(") => false,
+                Some(r) if env_substitutors.iter().any(|v| v == r) => false,
+                Some(r) if interpolators.iter().any(|v| v == r) => true,
+                // Some other object's replace()
+                Some(_) => continue,
+            };
+            let Some(literal) = ["dq", "sq", "raw"].iter().find_map(|g| cap.name(g)) else {
+                continue;
+            };
+
+            for r in reference.captures_iter(literal.as_str()) {
+                if env_only && r.name("prefix").is_none() {
+                    continue;
+                }
+                let name = r.name("braced").or_else(|| r.name("bare")).unwrap();
+                let default_value = r.name("default").map(|d| d.as_str().to_string());
+                usages.push(EnvVarUsage {
+                    name: name.as_str().to_string(),
+                    file_path: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    column: literal.start() + name.start() + 1,
+                    language,
+                    context: Some(line.trim().to_string()),
+                    required: default_value.as_ref().map(|_| false),
+                    default_value,
+                    exposure: None,
+                });
+            }
+        }
+    }
+
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(language: Language, content: &str) -> Vec<String> {
+        scan_expansions(language, content, Path::new("test"))
+            .into_iter()
+            .map(|u| u.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_go_expand_env() {
+        let content = r#"dir := os.ExpandEnv("$HOME/${APP_DIR}/cache")
+raw := os.ExpandEnv(`${XDG_CONFIG_HOME}/app`)
+custom := os.Expand("${NOT_ENV}", lookup)
+mapped := os.Expand("$LOG_DIR/app.log", os.Getenv)
+port := os.ExpandEnv("${PORT:-8080}")"#;
+        assert_eq!(
+            names(Language::Go, content),
+            vec!["HOME", "APP_DIR", "XDG_CONFIG_HOME", "LOG_DIR"]
+        );
+
+        let usages = scan_expansions(Language::Go, content, Path::new("main.go"));
+        assert_eq!(usages[1].column, 30);
+    }
+
+    #[test]
+    fn test_python_expandvars() {
+        let content = r#"path = os.path.expandvars("$DATA_ROOT/${DATASET}")
+other = expandvars('~/$USER')
+text = "$NOT_EXPANDED"
+port = os.path.expandvars("${PORT:-8080}")"#;
+        assert_eq!(
+            names(Language::Python, content),
+            vec!["DATA_ROOT", "DATASET", "USER"]
+        );
+    }
+
+    #[test]
+    fn test_rust_shellexpand_default() {
+        let content = r#"let dir = shellexpand::env("${CACHE_DIR:-/tmp/cache}/app")?;"#;
+        let usages = scan_expansions(Language::Rust, content, Path::new("main.rs"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "CACHE_DIR");
+        assert_eq!(usages[0].default_value.as_deref(), Some("/tmp/cache"));
+        assert!(usages[0].is_optional());
+    }
+
+    #[test]
+    fn test_java_string_substitutor() {
+        let env_backed = r#"StringSubstitutor sub = new StringSubstitutor(System.getenv());
+String url = sub.replace("jdbc:postgresql://${DB_HOST}:${DB_PORT:-5432}/app");"#;
+        assert_eq!(
+            names(Language::Java, env_backed),
+            vec!["DB_HOST", "DB_PORT"]
+        );
+
+        let interpolator = r#"StringSubstitutor interpolator = StringSubstitutor.createInterpolator();
+String home = interpolator.replace("${env:HOME} ${sys:user.dir} ${NOT_ENV}");"#;
+        assert_eq!(names(Language::Java, interpolator), vec!["HOME"]);
+
+        let unrelated = r#"String s = name.replace("${X}", "y");"#;
+        assert!(names(Language::Java, unrelated).is_empty());
+
+        // Other objects' replace() in a file that also uses an env substitutor
+        let mixed = r#"StringSubstitutor sub = new StringSubstitutor(System.getenv());
+String a = template.replace("${NOT_ENV}", "x");
+String b = sub.replace("${API_URL}");"#;
+        assert_eq!(names(Language::Java, mixed), vec!["API_URL"]);
+
+        let static_call =
+            r#"String url = StringSubstitutor.replace("${REDIS_URL}", System.getenv());"#;
+        assert_eq!(names(Language::Java, static_call), vec!["REDIS_URL"]);
+    }
+}
@@ -0,0 +1,452 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use crate::types::{EnvVarUsage, Exposure, Language};
+
+// import { A, B as b } from '$env/static/private' / 'astro:env/server'
+static ENV_MODULE_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\bimport\s*\{(?P<names>[^}]*)\}\s*from\s*['"](?P<module>\$env/(?:static|dynamic)/(?:private|public)|astro:env/(?:server|client))['"]"#,
+    )
+    .unwrap()
+});
+
+static SPECIFIER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?P<name>[A-Za-z_$][\w$]*)(?:\s+as\s+(?P<local>[A-Za-z_$][\w$]*))?").unwrap()
+});
+
+// const config = useRuntimeConfig()
+static RUNTIME_CONFIG_ALIAS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:const|let|var)\s+([A-Za-z_$][\w$]*)\s*=\s*useRuntimeConfig\(").unwrap()
+});
+
+// const { apiSecret, public: { apiBase } } = useRuntimeConfig()
+static RUNTIME_CONFIG_DESTRUCTURE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:const|let|var)\s*\{(?P<pattern>[^=;]*)\}\s*=\s*useRuntimeConfig\(").unwrap()
+});
+
+/// Members of strings, arrays and objects that end a runtime config chain,
+/// as in `config.apiSecret.length`
+const NON_KEY_MEMBERS: &[&str] = &[
+    "length",
+    "toString",
+    "toLocaleString",
+    "valueOf",
+    "toJSON",
+    "constructor",
+    "hasOwnProperty",
+    "split",
+    "trim",
+    "trimStart",
+    "trimEnd",
+    "replace",
+    "replaceAll",
+    "startsWith",
+    "endsWith",
+    "includes",
+    "indexOf",
+    "slice",
+    "substring",
+    "toLowerCase",
+    "toUpperCase",
+    "padStart",
+    "padEnd",
+    "concat",
+    "charAt",
+    "map",
+    "filter",
+    "forEach",
+    "some",
+    "every",
+    "join",
+];
+
+/// Usages read through framework env modules: SvelteKit `$env/*`, Astro
+/// `astro:env/*` and Nuxt `useRuntimeConfig()`, marked private or public by
+/// the module they come from
+pub(super) fn scan_env_modules(content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+    let mut usages = Vec::new();
+    let mut push = |name: &str, offset: usize, exposure: Exposure, required: Option<bool>| {
+        let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line = content[line_start..].lines().next().unwrap_or("");
+        usages.push(EnvVarUsage {
+            name: name.to_string(),
+            file_path: file_path.to_path_buf(),
+            line: content[..offset].matches('\n').count() + 1,
+            column: offset - line_start + 1,
+            language: Language::JavaScript,
+            context: Some(line.trim().to_string()),
+            default_value: None,
+            required,
+            exposure: Some(exposure),
+        });
+    };
+
+    // Objects whose members are env vars: `env` from $env/dynamic/*
+    let mut env_objects: Vec<(String, Exposure)> = Vec::new();
+    // Functions taking a var name: Astro's getSecret
+    let mut secret_getters: Vec<String> = Vec::new();
+
+    for cap in ENV_MODULE_IMPORT.captures_iter(content) {
+        let module = &cap["module"];
+        let exposure = if module.ends_with("private") || module.ends_with("server") {
+            Exposure::Private
+        } else {
+            Exposure::Public
+        };
+        let names = cap.name("names").unwrap();
+
+        for spec in SPECIFIER.captures_iter(names.as_str()) {
+            let name = spec.name("name").unwrap();
+            let local = spec.name("local").map_or(name.as_str(), |l| l.as_str());
+            if module.starts_with("$env/dynamic/") {
+                if name.as_str() == "env" {
+                    env_objects.push((local.to_string(), exposure));
+                }
+            } else if module == "astro:env/server" && name.as_str() == "getSecret" {
+                secret_getters.push(local.to_string());
+            } else if name.as_str() != "type" {
+                push(name.as_str(), names.start() + name.start(), exposure, None);
+            }
+        }
+    }
+
+    let runtime_configs: Vec<String> = RUNTIME_CONFIG_ALIAS
+        .captures_iter(content)
+        .map(|cap| cap[1].to_string())
+        .collect();
+
+    if env_objects.is_empty() && secret_getters.is_empty() && !content.contains("useRuntimeConfig")
+    {
+        return usages;
+    }
+
+    // env.X / env["X"] on the dynamic env objects
+    for (object, exposure) in &env_objects {
+        let access = Regex::new(&format!(
+            r#"(?:^|[^\w$.]){}(?:\??\.(?P<dot>[A-Za-z_][A-Za-z0-9_]*)|(?:\?\.)?\[\s*['"](?P<index>[A-Za-z_][A-Za-z0-9_]*)['"]\s*\])"#,
+            regex::escape(object)
+        ))
+        .unwrap();
+        for cap in access.captures_iter(content) {
+            let name = cap.name("dot").or_else(|| cap.name("index")).unwrap();
+            push(name.as_str(), name.start(), *exposure, None);
+        }
+    }
+
+    // getSecret("X")
+    for getter in &secret_getters {
+        let call = Regex::new(&format!(
+            r#"(?:^|[^\w$.]){}\(\s*['"](?P<name>[A-Za-z_][A-Za-z0-9_]*)['"]"#,
+            regex::escape(getter)
+        ))
+        .unwrap();
+        for cap in call.captures_iter(content) {
+            let name = cap.name("name").unwrap();
+            push(name.as_str(), name.start(), Exposure::Private, None);
+        }
+    }
+
+    // config.apiSecret / useRuntimeConfig().public.apiBase, overridden by
+    // NUXT_API_SECRET / NUXT_PUBLIC_API_BASE. Nuxt only overrides keys that
+    // nuxt.config declares with a default, so these are never required.
+    let mut public_aliases: Vec<String> = Vec::new();
+    for cap in RUNTIME_CONFIG_DESTRUCTURE.captures_iter(content) {
+        let pattern = cap.name("pattern").unwrap();
+        let mut keys = Vec::new();
+        destructured_keys(
+            pattern.as_str(),
+            pattern.start(),
+            &[],
+            &mut keys,
+            &mut public_aliases,
+        );
+        for (segments, offset) in keys {
+            let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+            if let Some((name, exposure)) = runtime_config_var(&segments) {
+                push(&name, offset, exposure, Some(false));
+            }
+        }
+    }
+
+    let heads: Vec<String> = runtime_configs
+        .iter()
+        .chain(&public_aliases)
+        .map(|alias| regex::escape(alias))
+        .chain([r"useRuntimeConfig\(\s*(?:[A-Za-z_$][\w$]*\s*)?\)".to_string()])
+        .collect();
+    let chain = Regex::new(&format!(
+        r"(?:^|[^\w$.])(?P<head>{})(?P<chain>(?:\??\.[A-Za-z_$][\w$]*)+)(?P<call>\s*\()?",
+        heads.join("|")
+    ))
+    .unwrap();
+    for cap in chain.captures_iter(content) {
+        let keys = cap.name("chain").unwrap();
+        let mut segments: Vec<&str> = keys
+            .as_str()
+            .split('.')
+            .map(|s| s.trim_end_matches('?'))
+            .filter(|s| !s.is_empty())
+            .collect();
+        // A trailing method call is not a config key
+        if cap.name("call").is_some() {
+            segments.pop();
+        }
+        if let Some(end) = segments.iter().position(|s| NON_KEY_MEMBERS.contains(s)) {
+            segments.truncate(end);
+        }
+        // `const { public: pub } = useRuntimeConfig()` then `pub.apiBase`
+        let via_public_alias = public_aliases.iter().any(|a| a == &cap["head"]);
+        if via_public_alias {
+            segments.insert(0, "public");
+        }
+        let Some((name, exposure)) = runtime_config_var(&segments) else {
+            continue;
+        };
+
+        // Point at the first key, after `.public` for public keys
+        let skipped = if exposure == Exposure::Public && !via_public_alias {
+            2
+        } else {
+            1
+        };
+        let first_key: usize = keys
+            .as_str()
+            .split('.')
+            .take(skipped)
+            .map(|s| s.len() + 1)
+            .sum();
+        push(&name, keys.start() + first_key, exposure, Some(false));
+    }
+
+    usages
+}
+
+/// The NUXT_* var overriding a runtime config key path such as
+/// `["public", "apiBase"]`. `app` keys are Nuxt's own and have no override.
+fn runtime_config_var(segments: &[&str]) -> Option<(String, Exposure)> {
+    let (exposure, keys) = match segments.split_first() {
+        Some((&"public", rest)) => (Exposure::Public, rest),
+        _ => (Exposure::Private, segments),
+    };
+    if keys.is_empty() || keys[0] == "app" {
+        return None;
+    }
+
+    let mut name = String::from("NUXT_");
+    if exposure == Exposure::Public {
+        name.push_str("PUBLIC_");
+    }
+    let keys_snake: Vec<String> = keys.iter().map(|s| screaming_snake(s)).collect();
+    name.push_str(&keys_snake.join("_"));
+    Some((name, exposure))
+}
+
+/// Collect the key paths a destructuring pattern binds, with the offset of
+/// each key; `base` is the pattern's offset in the file. A top-level
+/// `public: alias` is recorded as an alias for the public keys instead.
+fn destructured_keys(
+    pattern: &str,
+    base: usize,
+    path: &[String],
+    keys: &mut Vec<(Vec<String>, usize)>,
+    public_aliases: &mut Vec<String>,
+) {
+    let is_ident = |s: &str| {
+        !s.is_empty()
+            && !s.starts_with(|c: char| c.is_ascii_digit())
+            && s.chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    };
+
+    // Split on the commas outside nested patterns
+    let mut entries = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in pattern.char_indices() {
+        match c {
+            '{' | '[' | '(' => depth += 1,
+            '}' | ']' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                entries.push((start, &pattern[start..i]));
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    entries.push((start, &pattern[start..]));
+
+    for (start, entry) in entries {
+        let offset = base + start + (entry.len() - entry.trim_start().len());
+        let entry = entry.trim();
+        let (key, value) = match entry.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (entry.split('=').next().unwrap_or("").trim(), ""),
+        };
+        // `...rest` and computed keys name no config key
+        if !is_ident(key) {
+            continue;
+        }
+
+        let mut key_path = path.to_vec();
+        key_path.push(key.to_string());
+        if let Some(inner) = value.strip_prefix('{').and_then(|v| v.strip_suffix('}')) {
+            // `value` ends the entry, so its offset follows from the lengths
+            let inner_start = offset + entry.len() - value.len() + 1;
+            destructured_keys(inner, inner_start, &key_path, keys, public_aliases);
+            continue;
+        }
+        if path.is_empty() && key == "public" && is_ident(value) {
+            public_aliases.push(value.to_string());
+        } else {
+            keys.push((key_path, offset));
+        }
+    }
+}
+
+/// `apiBase` -> `API_BASE`, `apiURL` -> `API_URL`
+fn screaming_snake(key: &str) -> String {
+    let chars: Vec<char> = key.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(content: &str) -> Vec<(String, Exposure)> {
+        scan_env_modules(content, Path::new("+page.server.ts"))
+            .into_iter()
+            .map(|u| (u.name, u.exposure.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_sveltekit_modules() {
+        let content = r#"import {
+  DATABASE_URL,
+  STRIPE_KEY as stripeKey
+} from '$env/static/private';
+import { PUBLIC_SITE_URL } from '$env/static/public';
+import { env } from '$env/dynamic/private';
+import { env as publicEnv } from '$env/dynamic/public';
+
+const db = connect(env.REDIS_URL);
+const api = publicEnv["PUBLIC_API_BASE"];
+"#;
+        assert_eq!(
+            scan(content),
+            vec![
+                ("DATABASE_URL".to_string(), Exposure::Private),
+                ("STRIPE_KEY".to_string(), Exposure::Private),
+                ("PUBLIC_SITE_URL".to_string(), Exposure::Public),
+                ("REDIS_URL".to_string(), Exposure::Private),
+                ("PUBLIC_API_BASE".to_string(), Exposure::Public),
+            ]
+        );
+
+        let usages = scan_env_modules(content, Path::new("+page.server.ts"));
+        assert_eq!((usages[1].line, usages[1].column), (3, 3));
+    }
+
+    #[test]
+    fn test_astro_env() {
+        let content = r#"import { API_SECRET, getSecret } from "astro:env/server";
+import { PUBLIC_API_URL } from "astro:env/client";
+const key = getSecret("FEATURE_FLAG_KEY");
+"#;
+        assert_eq!(
+            scan(content),
+            vec![
+                ("API_SECRET".to_string(), Exposure::Private),
+                ("PUBLIC_API_URL".to_string(), Exposure::Public),
+                ("FEATURE_FLAG_KEY".to_string(), Exposure::Private),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nuxt_runtime_config() {
+        let content = r#"const config = useRuntimeConfig(event);
+const secret = config.apiSecret;
+const base = config.public.apiBase.replace(/\/$/, "");
+const url = useRuntimeConfig().public.siteURL;
+"#;
+        let usages = scan_env_modules(content, Path::new("server/api/hello.ts"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "NUXT_API_SECRET",
+                "NUXT_PUBLIC_API_BASE",
+                "NUXT_PUBLIC_SITE_URL"
+            ]
+        );
+        assert_eq!(usages[0].exposure, Some(Exposure::Private));
+        assert_eq!(usages[1].exposure, Some(Exposure::Public));
+        assert!(usages[0].is_optional());
+        assert_eq!(usages[0].column, 23);
+    }
+
+    #[test]
+    fn test_nuxt_chain_stops_at_non_keys() {
+        let content = r#"const config = useRuntimeConfig();
+if (config.apiSecret.length === 0) throw new Error();
+const key = config.public.apiKey.toString();
+const base = useRuntimeConfig(event).public.apiBase;
+"#;
+        let names: Vec<_> = scan(content).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "NUXT_API_SECRET",
+                "NUXT_PUBLIC_API_KEY",
+                "NUXT_PUBLIC_API_BASE"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nuxt_destructuring() {
+        let content = r#"const { apiSecret, db: { host }, public: { apiBase } } = useRuntimeConfig(event);
+const { public: pub, app } = useRuntimeConfig();
+const url = pub.siteUrl;
+"#;
+        let usages = scan_env_modules(content, Path::new("server/api/hello.ts"));
+        let found: Vec<_> = usages
+            .iter()
+            .map(|u| (u.name.as_str(), u.exposure.unwrap(), u.column))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("NUXT_API_SECRET", Exposure::Private, 9),
+                ("NUXT_DB_HOST", Exposure::Private, 26),
+                ("NUXT_PUBLIC_API_BASE", Exposure::Public, 44),
+                ("NUXT_PUBLIC_SITE_URL", Exposure::Public, 17),
+            ]
+        );
+        assert!(usages.iter().all(|u| u.is_optional()));
+    }
+
+    #[test]
+    fn test_screaming_snake() {
+        assert_eq!(screaming_snake("apiBase"), "API_BASE");
+        assert_eq!(screaming_snake("apiURL"), "API_URL");
+        assert_eq!(screaming_snake("OAuthToken"), "O_AUTH_TOKEN");
+    }
+}
@@ -1,26 +1,472 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{expansion, GoNestedField, LanguageScanner, ScanContext};
 use crate::types::{EnvVarUsage, Language};
 
 /// Scanner for Go files
 pub struct GoScanner;
 
 static OS_GETENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.Getenv\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"os\.Getenv\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static OS_LOOKUP_ENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.LookupEnv\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"os\.LookupEnv\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static OS_SETENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.Setenv\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"os\.Setenv\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
+
+// Struct definitions and tagged fields
+static STRUCT_DEF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*type\s+(\w+)\s+struct\s*\{"#).unwrap());
+
+static TAGGED_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*(\w+)\s+([\w.*\[\]]+)\s*(?:`([^`]*)`)?"#).unwrap());
+
+static TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r#"`([^`]*)`"#).unwrap());
+
+static TAG_PAIR: Lazy<Regex> = Lazy::new(|| Regex::new(r#"(\w+):"([^"]*)""#).unwrap());
+
+// envconfig.Process("prefix", &spec)
+static ENVCONFIG_PROCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"envconfig\.(?:Must)?Process\s*\(\s*"([^"]*)"\s*,\s*&?(\w+)"#).unwrap()
+});
+
+// var cfg Config / cfg := Config{} / cfg := &Config{}
+static VAR_DECL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bvar\s+(\w+)\s+\*?([\w.]+)|(\w+)\s*:?=\s*&?([\w.]+)\s*\{"#).unwrap()
+});
+
+// viper
+static VIPER_BIND_ENV: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b\w+\.BindEnv\s*\(\s*"([^"]+)"(?:\s*,\s*"([A-Za-z_][A-Za-z0-9_]*)")?"#).unwrap()
+});
+
+static VIPER_ENV_PREFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(\w+)\.SetEnvPrefix\s*\(\s*"([^"]*)""#).unwrap());
+
+static VIPER_AUTOMATIC_ENV: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(\w+)\.AutomaticEnv\s*\(\s*\)"#).unwrap());
+
+static VIPER_KEY_REPLACER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"SetEnvKeyReplacer\s*\(\s*strings\.NewReplacer\s*\(([^)]*)\)"#).unwrap()
+});
+
+static VIPER_DEFAULT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b\w+\.SetDefault\s*\(\s*"([^"]+)"\s*,\s*"?([^")]*)"?"#).unwrap());
+
+static STRING_LITERAL: Lazy<Regex> = Lazy::new(|| Regex::new(r#""([^"]*)""#).unwrap());
+
+/// How deep prefixes are followed through nested structs, which also stops
+/// at recursive types
+const MAX_NESTING: usize = 8;
+
+/// A struct field with its parsed tags
+struct GoField {
+    name: String,
+    ty: String,
+    tags: HashMap<String, String>,
+    line: usize,
+    column: usize,
+    context: String,
+}
 
 impl GoScanner {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse struct definitions and the tags of their fields. The fields of
+    /// an anonymous inner struct go to a struct named `Outer.Field`, which
+    /// is the type of the field holding it.
+    fn parse_structs(content: &str) -> HashMap<String, Vec<GoField>> {
+        let mut structs: HashMap<String, Vec<GoField>> = HashMap::new();
+        // Structs being parsed, innermost last, with the index of the field
+        // holding each anonymous one in its parent
+        let mut open: Vec<(String, Option<usize>)> = Vec::new();
+        let parse_tags = |tags: &str| -> HashMap<String, String> {
+            TAG_PAIR
+                .captures_iter(tags)
+                .map(|p| (p[1].to_string(), p[2].to_string()))
+                .collect()
+        };
+
+        for (line_num, line) in content.lines().enumerate() {
+            // Braces inside tags are not Go syntax
+            let code = line.split('`').next().unwrap_or("");
+
+            let Some((struct_name, _)) = open.last() else {
+                // `type Empty struct{}` has no fields to parse
+                if let Some(cap) = STRUCT_DEF.captures(line) {
+                    if brace_delta(code) > 0 {
+                        open.push((cap[1].to_string(), None));
+                        structs.entry(cap[1].to_string()).or_default();
+                    }
+                }
+                continue;
+            };
+            let struct_name = struct_name.clone();
+
+            if code.trim_start().starts_with('}') {
+                let (_, holder) = open.pop().unwrap();
+                // An anonymous struct's tags follow its closing brace
+                if let (Some(index), Some((parent, _))) = (holder, open.last()) {
+                    let tags = TAG.captures(line).map(|t| parse_tags(&t[1]));
+                    if let (Some(tags), Some(fields)) = (tags, structs.get_mut(parent)) {
+                        fields[index].tags = tags;
+                    }
+                }
+                continue;
+            }
+
+            if let Some(cap) = TAGGED_FIELD.captures(line) {
+                let name = cap.get(1).unwrap();
+                let mut field = GoField {
+                    name: name.as_str().to_string(),
+                    ty: type_name(&cap[2]).to_string(),
+                    tags: cap
+                        .get(3)
+                        .map(|t| parse_tags(t.as_str()))
+                        .unwrap_or_default(),
+                    line: line_num + 1,
+                    column: name.start() + 1,
+                    context: line.trim().to_string(),
+                };
+                let anonymous = field.ty == "struct" && brace_delta(code) > 0;
+                if anonymous {
+                    field.ty = format!("{}.{}", struct_name, field.name);
+                    structs.entry(field.ty.clone()).or_default();
+                }
+
+                let anonymous_ty = anonymous.then(|| field.ty.clone());
+                if let Some(fields) = structs.get_mut(&struct_name) {
+                    fields.push(field);
+                    if let Some(ty) = anonymous_ty {
+                        open.push((ty, Some(fields.len() - 1)));
+                    }
+                }
+            }
+        }
+
+        structs
+    }
+
+    /// Record this file's structs and `envconfig.Process` calls, so nested
+    /// prefixes resolve across files
+    fn collect_structs(content: &str, context: &mut ScanContext) {
+        for (struct_name, fields) in Self::parse_structs(content) {
+            let nested = fields
+                .iter()
+                .filter(|f| f.ty.starts_with(|c: char| c.is_ascii_uppercase()))
+                .map(|f| GoNestedField {
+                    ty: f.ty.clone(),
+                    env_prefix: f.tags.get("envPrefix").cloned(),
+                    envconfig_key: envconfig_key(f),
+                });
+            context
+                .go_struct_fields
+                .entry(struct_name)
+                .or_default()
+                .extend(nested);
+        }
+
+        // Variable name -> struct type
+        let mut var_types: HashMap<&str, &str> = HashMap::new();
+        for cap in VAR_DECL.captures_iter(content) {
+            if let (Some(var), Some(ty)) = (cap.get(1).or(cap.get(3)), cap.get(2).or(cap.get(4))) {
+                var_types.insert(var.as_str(), type_name(ty.as_str()));
+            }
+        }
+        for cap in ENVCONFIG_PROCESS.captures_iter(content) {
+            let target = cap.get(2).unwrap().as_str();
+            let ty = var_types.get(target).copied().unwrap_or(target);
+            context
+                .go_envconfig_prefixes
+                .entry(ty.to_string())
+                .or_default()
+                .insert(cap[1].to_uppercase());
+        }
+    }
+
+    /// Prefixes caarlos0/env puts on the fields of `ty`: the `envPrefix` tags
+    /// of the fields holding it, joined from the outermost struct down
+    fn env_prefixes(context: &ScanContext, ty: &str, depth: usize) -> BTreeSet<String> {
+        let mut prefixes = BTreeSet::new();
+        if depth > MAX_NESTING {
+            return prefixes;
+        }
+        let mut nested = false;
+        for (parent, fields) in &context.go_struct_fields {
+            for field in fields.iter().filter(|f| f.ty == ty) {
+                nested = true;
+                let own = field.env_prefix.as_deref().unwrap_or("");
+                for outer in Self::env_prefixes(context, parent, depth + 1) {
+                    prefixes.insert(format!("{}{}", outer, own));
+                }
+            }
+        }
+        if !nested {
+            prefixes.insert(String::new());
+        }
+        prefixes
+    }
+
+    /// Prefixes envconfig reads the fields of `ty` with: those it is
+    /// processed with, and `PREFIX_FIELD` for every field holding it in a
+    /// processed struct. Empty when `ty` is never processed.
+    fn envconfig_prefixes(context: &ScanContext, ty: &str, depth: usize) -> BTreeSet<String> {
+        let mut prefixes = context
+            .go_envconfig_prefixes
+            .get(ty)
+            .cloned()
+            .unwrap_or_default();
+        if depth > MAX_NESTING {
+            return prefixes;
+        }
+        for (parent, fields) in &context.go_struct_fields {
+            for field in fields.iter().filter(|f| f.ty == ty) {
+                for outer in Self::envconfig_prefixes(context, parent, depth + 1) {
+                    prefixes.insert(if outer.is_empty() {
+                        field.envconfig_key.clone()
+                    } else {
+                        format!("{}_{}", outer, field.envconfig_key)
+                    });
+                }
+            }
+        }
+        prefixes
+    }
+
+    /// Usages from caarlos0/env `env:"X"` tags, following `envPrefix` on nested structs
+    fn env_tag_usages(
+        structs: &HashMap<String, Vec<GoField>>,
+        context: &ScanContext,
+        file_path: &Path,
+        usages: &mut Vec<EnvVarUsage>,
+    ) {
+        for (struct_name, fields) in structs {
+            let prefixes = Self::env_prefixes(context, struct_name, 0);
+
+            for field in fields {
+                let Some(tag) = field.tags.get("env") else {
+                    continue;
+                };
+                let mut parts = tag.split(',');
+                let name = parts.next().unwrap_or("").trim();
+                if name.is_empty() || name == "-" {
+                    continue;
+                }
+                let options: Vec<&str> = parts.map(str::trim).collect();
+
+                for prefix in &prefixes {
+                    usages.push(EnvVarUsage {
+                        name: format!("{}{}", prefix, name),
+                        file_path: file_path.to_path_buf(),
+                        line: field.line,
+                        column: field.column,
+                        language: Language::Go,
+                        context: Some(field.context.clone()),
+                        default_value: field.tags.get("envDefault").cloned(),
+                        required: Some(
+                            options.contains(&"required") || options.contains(&"notEmpty"),
+                        ),
+                        exposure: None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Usages from kelseyhightower/envconfig structs. A struct-typed field
+    /// adds its key to the prefix of the struct it holds.
+    fn envconfig_usages(
+        structs: &HashMap<String, Vec<GoField>>,
+        context: &ScanContext,
+        file_path: &Path,
+        usages: &mut Vec<EnvVarUsage>,
+    ) {
+        for (struct_name, fields) in structs {
+            let prefixes = Self::envconfig_prefixes(context, struct_name, 0);
+
+            for field in fields {
+                // Struct-typed fields are not read as a single var
+                if context.go_struct_fields.contains_key(&field.ty) {
+                    continue;
+                }
+                // Skip fields already reported through an `env` tag
+                if field.tags.contains_key("env") && !field.tags.contains_key("envconfig") {
+                    continue;
+                }
+                let keys: Vec<String> = match field.tags.get("envconfig") {
+                    Some(tag) if tag.is_empty() || tag == "-" => continue,
+                    // Untagged fields are only read when the struct is processed
+                    None if prefixes.is_empty() => continue,
+                    Some(tag) if prefixes.is_empty() => vec![tag.clone()],
+                    _ => {
+                        let key = envconfig_key(field);
+                        prefixes
+                            .iter()
+                            .map(|p| {
+                                if p.is_empty() {
+                                    key.clone()
+                                } else {
+                                    format!("{}_{}", p, key)
+                                }
+                            })
+                            .collect()
+                    }
+                };
+
+                for key in keys {
+                    usages.push(EnvVarUsage {
+                        name: key,
+                        file_path: file_path.to_path_buf(),
+                        line: field.line,
+                        column: field.column,
+                        language: Language::Go,
+                        context: Some(field.context.clone()),
+                        default_value: field.tags.get("default").cloned(),
+                        required: Some(
+                            field.tags.get("required").map(String::as_str) == Some("true"),
+                        ),
+                        exposure: None,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Usages from viper `BindEnv` and `AutomaticEnv` lookups
+    fn viper_usages(content: &str, file_path: &Path, usages: &mut Vec<EnvVarUsage>) {
+        let prefix = VIPER_ENV_PREFIX
+            .captures(content)
+            .map(|c| format!("{}_", c[2].to_uppercase()))
+            .unwrap_or_default();
+
+        let replacer: Vec<(String, String)> = VIPER_KEY_REPLACER
+            .captures(content)
+            .map(|c| {
+                let args: Vec<String> = STRING_LITERAL
+                    .captures_iter(&c[1])
+                    .map(|a| a[1].to_string())
+                    .collect();
+                args.chunks(2)
+                    .filter(|pair| pair.len() == 2)
+                    .map(|pair| (pair[0].clone(), pair[1].clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let env_key = |key: &str| {
+            let mut name = format!("{}{}", prefix, key.to_uppercase());
+            for (from, to) in &replacer {
+                name = name.replace(from.as_str(), to);
+            }
+            name
+        };
+
+        let defaults: HashMap<String, String> = VIPER_DEFAULT
+            .captures_iter(content)
+            .map(|c| (env_key(&c[1]), c[2].trim().to_string()))
+            .collect();
+
+        let automatic_env: Vec<String> = VIPER_AUTOMATIC_ENV
+            .captures_iter(content)
+            .map(|c| c[1].to_string())
+            .collect();
+        let getter = Regex::new(&format!(
+            r#"\b(?:{})\.(?:Get\w*|IsSet)\s*\(\s*"([^"]+)""#,
+            automatic_env.join("|")
+        ))
+        .ok()
+        .filter(|_| !automatic_env.is_empty());
+
+        for (line_num, line) in content.lines().enumerate() {
+            let mut found: Vec<(usize, String)> = Vec::new();
+
+            for cap in VIPER_BIND_ENV.captures_iter(line) {
+                match cap.get(2) {
+                    Some(m) => found.push((m.start(), m.as_str().to_string())),
+                    None => found.push((cap.get(1).unwrap().start(), env_key(&cap[1]))),
+                }
+            }
+
+            if let Some(getter) = &getter {
+                for cap in getter.captures_iter(line) {
+                    found.push((cap.get(1).unwrap().start(), env_key(&cap[1])));
+                }
+            }
+
+            for (start, name) in found {
+                if !is_env_name(&name) {
+                    continue;
+                }
+                usages.push(EnvVarUsage {
+                    default_value: defaults.get(&name).cloned(),
+                    name,
+                    file_path: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    column: start + 1,
+                    language: Language::Go,
+                    context: Some(line.trim().to_string()),
+                    required: None,
+                    exposure: None,
+                });
+            }
+        }
+    }
+}
+
+/// Split a Go field name into upper snake case words, as envconfig's `split_words` does
+fn split_words(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let boundary = i > 0
+            && c.is_uppercase()
+            && (chars[i - 1].is_lowercase()
+                || chars.get(i + 1).map(|n| n.is_lowercase()).unwrap_or(false));
+        if boundary {
+            out.push('_');
+        }
+        out.extend(c.to_uppercase());
+    }
+    out
+}
+
+fn brace_delta(line: &str) -> i32 {
+    line.chars().fold(0, |acc, c| match c {
+        '{' => acc + 1,
+        '}' => acc - 1,
+        _ => acc,
+    })
+}
+
+/// Type of a field without pointer or package qualifier: `*config.DB` -> `DB`
+fn type_name(ty: &str) -> &str {
+    let ty = ty.trim_start_matches('*');
+    if ty.starts_with('[') {
+        return ty;
+    }
+    ty.rsplit('.').next().unwrap_or(ty)
+}
+
+/// Key envconfig reads a field with before any prefix: its `envconfig` tag,
+/// or its name, split into words when `split_words` is set
+fn envconfig_key(field: &GoField) -> String {
+    match field.tags.get("envconfig") {
+        Some(tag) if !tag.is_empty() && tag != "-" => tag.clone(),
+        _ if field.tags.get("split_words").map(String::as_str) == Some("true") => {
+            split_words(&field.name)
+        }
+        _ => field.name.to_uppercase(),
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
 }
 
 impl Default for GoScanner {
@@ -39,6 +485,19 @@ impl LanguageScanner for GoScanner {
     }
 
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        self.scan_with_context(content, file_path, &ScanContext::default())
+    }
+
+    fn collect_context(&self, content: &str, _file_path: &Path, context: &mut ScanContext) {
+        Self::collect_structs(content, context);
+    }
+
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
         let mut usages = Vec::new();
         let patterns: Vec<&Lazy<Regex>> = vec![&OS_GETENV, &OS_LOOKUP_ENV, &OS_SETENV];
 
@@ -55,12 +514,35 @@ impl LanguageScanner for GoScanner {
                             column: m.start() + 1,
                             language: Language::Go,
                             context: Some(line.trim().to_string()),
+                            default_value: None,
+                            required: None,
+                            exposure: None,
                         });
                     }
                 }
             }
         }
 
+        let structs = Self::parse_structs(content);
+        // Scanned on its own, the file is its own context
+        let local;
+        let context = if structs
+            .keys()
+            .all(|s| context.go_struct_fields.contains_key(s))
+        {
+            context
+        } else {
+            let mut file_context = ScanContext::default();
+            Self::collect_structs(content, &mut file_context);
+            local = file_context;
+            &local
+        };
+        Self::env_tag_usages(&structs, context, file_path, &mut usages);
+        Self::envconfig_usages(&structs, context, file_path, &mut usages);
+        Self::viper_usages(content, file_path, &mut usages);
+        usages.extend(expansion::scan_expansions(Language::Go, content, file_path));
+
+        usages.sort_by_key(|u| (u.line, u.column));
         usages
     }
 }
@@ -86,4 +568,185 @@ mod tests {
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "DATABASE_URL");
     }
+
+    #[test]
+    fn test_env_struct_tags() {
+        let scanner = GoScanner::new();
+        let content = r#"
+type Config struct {
+    Port     int    `env:"PORT" envDefault:"8080"`
+    Database DBConfig `envPrefix:"DB_"`
+    Secret   string `env:"SECRET,required"`
+}
+
+type DBConfig struct {
+    Host string `env:"HOST"`
+}
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["PORT", "SECRET", "DB_HOST"]);
+        assert_eq!(usages[0].default_value.as_deref(), Some("8080"));
+        assert_eq!(usages[1].required, Some(true));
+    }
+
+    #[test]
+    fn test_anonymous_inner_struct() {
+        let scanner = GoScanner::new();
+        let content = r#"
+type Config struct {
+    Port  int `env:"PORT"`
+    Redis struct {
+        Addr string `env:"ADDR" envDefault:"localhost:6379"`
+    } `envPrefix:"REDIS_"`
+    Seen   map[string]struct{}
+    Secret string `env:"SECRET,required"`
+}
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        let mut names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["PORT", "REDIS_ADDR", "SECRET"]);
+    }
+
+    #[test]
+    fn test_envconfig_prefix() {
+        let scanner = GoScanner::new();
+        let content = r#"
+type Spec struct {
+    Debug      bool
+    MaxWorkers int    `split_words:"true" default:"4"`
+    User       string `envconfig:"USERNAME" required:"true"`
+}
+
+func load() {
+    var s Spec
+    envconfig.Process("myapp", &s)
+}
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["MYAPP_DEBUG", "MYAPP_MAX_WORKERS", "MYAPP_USERNAME"]
+        );
+        assert_eq!(usages[1].default_value.as_deref(), Some("4"));
+        assert_eq!(usages[2].required, Some(true));
+    }
+
+    #[test]
+    fn test_nested_prefixes_compose() {
+        let scanner = GoScanner::new();
+        let content = r#"
+type Config struct {
+    Database DBConfig `envPrefix:"DB_"`
+}
+
+type DBConfig struct {
+    Host    string        `env:"HOST"`
+    Replica ReplicaConfig `envPrefix:"REPLICA_"`
+}
+
+type ReplicaConfig struct {
+    Host string `env:"HOST"`
+}
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["DB_HOST", "DB_REPLICA_HOST"]);
+
+        let content = r#"
+type Spec struct {
+    Database Database
+}
+
+type Database struct {
+    Pool Pool `envconfig:"POOL"`
+}
+
+type Pool struct {
+    MaxConns int `split_words:"true"`
+}
+
+func load() {
+    var s Spec
+    envconfig.Process("app", &s)
+}
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["APP_DATABASE_POOL_MAX_CONNS"]);
+    }
+
+    #[test]
+    fn test_prefixes_across_files() {
+        let scanner = GoScanner::new();
+        let spec = r#"
+type Spec struct {
+    Port  int
+    Redis RedisConfig
+    Cache CacheConfig `envPrefix:"CACHE_"`
+}
+"#;
+        let redis = r#"
+type RedisConfig struct {
+    Addr string
+}
+
+type CacheConfig struct {
+    TTL int `env:"TTL"`
+}
+"#;
+        let main = r#"
+func main() {
+    cfg := &config.Spec{}
+    envconfig.MustProcess("app", cfg)
+}
+"#;
+        let mut context = ScanContext::default();
+        scanner.collect_context(spec, Path::new("config/spec.go"), &mut context);
+        scanner.collect_context(redis, Path::new("config/redis.go"), &mut context);
+        scanner.collect_context(main, Path::new("cmd/main.go"), &mut context);
+
+        let names = |content: &str, file: &str| -> Vec<String> {
+            scanner
+                .scan_with_context(content, Path::new(file), &context)
+                .into_iter()
+                .map(|u| u.name)
+                .collect()
+        };
+        assert_eq!(names(spec, "config/spec.go"), vec!["APP_PORT"]);
+        assert_eq!(
+            names(redis, "config/redis.go"),
+            vec!["APP_REDIS_ADDR", "CACHE_TTL"]
+        );
+    }
+
+    #[test]
+    fn test_viper_bind_env() {
+        let scanner = GoScanner::new();
+        let content = r#"viper.BindEnv("database.url", "DATABASE_URL")"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "DATABASE_URL");
+    }
+
+    #[test]
+    fn test_viper_automatic_env() {
+        let scanner = GoScanner::new();
+        let content = r#"
+viper.SetEnvPrefix("app")
+viper.SetEnvKeyReplacer(strings.NewReplacer(".", "_"))
+viper.AutomaticEnv()
+viper.SetDefault("db.url", "postgres://localhost")
+url := viper.GetString("db.url")
+"#;
+        let usages = scanner.scan(content, Path::new("config.go"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "APP_DB_URL");
+        assert_eq!(
+            usages[0].default_value.as_deref(),
+            Some("postgres://localhost")
+        );
+    }
 }
@@ -0,0 +1,541 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Component, Path};
+
+use crate::types::Language;
+
+/// A module a source file imports, and the local names the import binds
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Import {
+    /// Module paths the import may refer to, as lower-cased components;
+    /// `*` matches any one component
+    paths: Vec<Vec<String>>,
+    /// Whether the paths were resolved from the importing file's directory,
+    /// so name a module by its full path rather than its tail
+    relative: bool,
+    /// Names the import binds in the importing file, such as `env` in
+    /// `import * as env from './env'`
+    pub bindings: Vec<String>,
+}
+
+impl Import {
+    /// Whether the import refers to the module with the given id, as built
+    /// by `module_id`
+    pub(super) fn names(&self, module: &[String]) -> bool {
+        self.paths.iter().any(|path| {
+            if path.is_empty() || path.len() > module.len() {
+                return false;
+            }
+            if self.relative && path.len() != module.len() {
+                return false;
+            }
+            let tail = &module[module.len() - path.len()..];
+            path.iter().zip(tail).all(|(p, m)| p == "*" || p == m)
+        })
+    }
+}
+
+static JS_IMPORT_FROM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^\s*import\s+(?:type\s+)?(?P<bindings>[^'";]*?)\s*\bfrom\s*['"](?P<spec>[^'"]+)['"]"#,
+    )
+    .unwrap()
+});
+static JS_IMPORT_BARE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?m)^\s*import\s*['"](?P<spec>[^'"]+)['"]"#).unwrap());
+static JS_REQUIRE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?:\b(?:const|let|var)\s+(?P<bindings>[^=;]+?)\s*=\s*)?\brequire\(\s*['"](?P<spec>[^'"]+)['"]\s*\)"#,
+    )
+    .unwrap()
+});
+
+static PY_FROM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^[ \t]*from[ \t]+(?P<spec>\.*[\w.]*)[ \t]+import[ \t]+(?P<names>\([^)]*\)|[^\n#]+)",
+    )
+    .unwrap()
+});
+static PY_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*import[ \t]+(?P<modules>[\w.]+(?:[ \t]+as[ \t]+\w+)?(?:[ \t]*,[ \t]*[\w.]+(?:[ \t]+as[ \t]+\w+)?)*)")
+        .unwrap()
+});
+
+static RUBY_REQUIRE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?m)^\s*(?P<kind>require_relative|require|load)\b\s*\(?\s*['"](?P<spec>[^'"]+)['"]"#,
+    )
+    .unwrap()
+});
+
+static PHP_USE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^\s*use\s+(?:function\s+|const\s+)?\\?(?P<spec>[\w\\]+)(?:\s+as\s+(?P<alias>\w+))?\s*;",
+    )
+    .unwrap()
+});
+static PHP_INCLUDE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(?:require|include)(?:_once)?\s*\(?\s*(?P<dir>__DIR__\s*\.\s*)?['"](?P<spec>[^'"]+)['"]"#,
+    )
+    .unwrap()
+});
+
+static RUST_USE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+(?P<spec>[\w:]+?)(?:::\{(?P<group>[^}]*)\})?(?:\s+as\s+(?P<alias>\w+))?\s*;",
+    )
+    .unwrap()
+});
+static RUST_MOD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(?P<name>\w+)\s*;").unwrap());
+
+static GO_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(?:import\s+)?(?:(?P<alias>[\w.]+)\s+)?"(?P<spec>[^"\s]+)"\s*$"#).unwrap()
+});
+
+static JAVA_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*import\s+(?P<static>static\s+)?(?P<spec>[\w.]+?)(?P<star>\.\*)?\s*;")
+        .unwrap()
+});
+
+static IDENTIFIER: Lazy<Regex> = Lazy::new(|| Regex::new(r"[A-Za-z_$][\w$]*").unwrap());
+
+/// Words in an import clause that bind nothing
+const IMPORT_KEYWORDS: &[&str] = &["as", "type", "typeof", "default", "const", "let", "var"];
+
+/// Extensions an import specifier may spell out
+const SOURCE_EXTENSIONS: &[&str] = &[
+    "js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx", "py", "rb", "php", "rs",
+];
+
+/// The id `Import::names` matches a module against: its path without the
+/// extension, and without the file name for index files such as `index.ts`,
+/// `__init__.py` and `mod.rs`. A Go package is its directory.
+pub(super) fn module_id(file: &Path, language: Language) -> Vec<String> {
+    let mut id = components(file);
+    if language == Language::Go {
+        id.pop();
+        return id;
+    }
+    if let Some(last) = id.last_mut() {
+        if let Some((stem, _)) = last.rsplit_once('.') {
+            *last = stem.to_string();
+        }
+    }
+    strip_index(&mut id);
+    id
+}
+
+/// The modules `content` imports, with relative specifiers resolved against
+/// the directory of `file_path`
+pub(super) fn file_imports(language: Language, content: &str, file_path: &Path) -> Vec<Import> {
+    let dir = file_path.parent().unwrap_or(Path::new(""));
+    let mut imports = Vec::new();
+
+    match language {
+        Language::JavaScript | Language::TypeScript => {
+            let specs = JS_IMPORT_FROM
+                .captures_iter(content)
+                .chain(JS_IMPORT_BARE.captures_iter(content))
+                .chain(JS_REQUIRE.captures_iter(content));
+            for cap in specs {
+                let spec = &cap["spec"];
+                let bindings = cap
+                    .name("bindings")
+                    .map(|b| identifiers(b.as_str()))
+                    .unwrap_or_default();
+                imports.push(if spec.starts_with('.') {
+                    relative(dir, spec, bindings)
+                } else {
+                    // Drop aliases and scopes such as `@/`, `~/`, `$lib/`, `@acme/`
+                    let parts = spec
+                        .split('/')
+                        .skip_while(|p| !p.starts_with(|c: char| c.is_alphanumeric()));
+                    package(parts, bindings)
+                });
+            }
+        }
+        Language::Python => {
+            for cap in PY_FROM.captures_iter(content) {
+                let spec = &cap["spec"];
+                let dots = spec.len() - spec.trim_start_matches('.').len();
+                let module: Vec<String> = spec[dots..]
+                    .split('.')
+                    .filter(|p| !p.is_empty())
+                    .map(str::to_lowercase)
+                    .collect();
+                let (relative, base) = if dots > 0 {
+                    let mut base = components(dir);
+                    for _ in 1..dots {
+                        base.pop();
+                    }
+                    base.extend(module);
+                    (true, base)
+                } else {
+                    (false, module)
+                };
+
+                // `from app import settings` may import the module app.settings
+                let names = cap["names"].trim_matches(|c: char| c == '(' || c == ')');
+                let mut paths = vec![base.clone()];
+                let mut bindings = Vec::new();
+                for item in names.split(',') {
+                    let mut words = item.split_whitespace();
+                    let Some(name) = words.next().filter(|n| *n != "*") else {
+                        continue;
+                    };
+                    let mut path = base.clone();
+                    path.push(name.to_lowercase());
+                    paths.push(path);
+                    bindings.push(words.nth(1).unwrap_or(name).to_string());
+                }
+                imports.push(Import {
+                    paths,
+                    relative,
+                    bindings,
+                });
+            }
+            for cap in PY_IMPORT.captures_iter(content) {
+                for item in cap["modules"].split(',') {
+                    let mut words = item.split_whitespace();
+                    let Some(module) = words.next() else {
+                        continue;
+                    };
+                    let parts: Vec<&str> = module.split('.').collect();
+                    // `import a.b` binds `a`, and calls go through `a.b.f()`
+                    let bindings = match words.nth(1) {
+                        Some(alias) => vec![alias.to_string()],
+                        None => vec![parts[0].to_string(), parts[parts.len() - 1].to_string()],
+                    };
+                    imports.push(package(parts.into_iter(), bindings));
+                }
+            }
+        }
+        Language::Ruby => {
+            for cap in RUBY_REQUIRE.captures_iter(content) {
+                let spec = &cap["spec"];
+                imports.push(if &cap["kind"] == "require_relative" {
+                    relative(dir, spec, Vec::new())
+                } else {
+                    package(spec.split('/'), Vec::new())
+                });
+            }
+        }
+        Language::Php => {
+            for cap in PHP_USE.captures_iter(content) {
+                let parts: Vec<&str> = cap["spec"].split('\\').filter(|p| !p.is_empty()).collect();
+                let binding = cap
+                    .name("alias")
+                    .map(|a| a.as_str())
+                    .or(parts.last().copied())
+                    .unwrap_or_default()
+                    .to_string();
+                imports.push(package(parts.into_iter(), vec![binding]));
+            }
+            for cap in PHP_INCLUDE.captures_iter(content) {
+                let spec = &cap["spec"];
+                imports.push(if cap.name("dir").is_some() || spec.starts_with('.') {
+                    relative(dir, spec.trim_start_matches('/'), Vec::new())
+                } else {
+                    package(spec.split('/'), Vec::new())
+                });
+            }
+        }
+        Language::Rust => {
+            // Directory of the module the file defines
+            let mut module_dir = components(dir);
+            if let Some(stem) = file_path.file_stem().and_then(|s| s.to_str()) {
+                if !matches!(stem, "mod" | "lib" | "main") {
+                    module_dir.push(stem.to_lowercase());
+                }
+            }
+
+            for cap in RUST_USE.captures_iter(content) {
+                let mut parts: Vec<&str> =
+                    cap["spec"].split("::").filter(|p| !p.is_empty()).collect();
+                let mut relative = false;
+                let mut base = Vec::new();
+                match parts.first() {
+                    Some(&"crate") => {
+                        parts.remove(0);
+                    }
+                    Some(&"self") | Some(&"super") => {
+                        relative = true;
+                        base = module_dir.clone();
+                        while let Some(head) = parts.first().copied() {
+                            match head {
+                                "self" => {}
+                                "super" => {
+                                    base.pop();
+                                }
+                                _ => break,
+                            }
+                            parts.remove(0);
+                        }
+                    }
+                    _ => {}
+                }
+                let mut path = base;
+                path.extend(parts.iter().map(|p| p.to_lowercase()));
+
+                // `use crate::env::require_env` imports an item of the module
+                let mut paths = vec![path.clone(), path[..path.len().saturating_sub(1)].to_vec()];
+                let mut bindings = Vec::new();
+                match cap.name("group") {
+                    Some(group) => {
+                        for item in group.as_str().split(',') {
+                            let mut words = item.split_whitespace();
+                            let Some(name) = words.next() else {
+                                continue;
+                            };
+                            let binding = words.nth(1).unwrap_or(name);
+                            if name == "self" {
+                                bindings.push(parts.last().copied().unwrap_or(binding).to_string());
+                                continue;
+                            }
+                            let mut member = path.clone();
+                            member.push(name.to_lowercase());
+                            paths.push(member);
+                            bindings.push(binding.to_string());
+                        }
+                    }
+                    None => bindings.extend(
+                        cap.name("alias")
+                            .map(|a| a.as_str())
+                            .or(parts.last().copied())
+                            .map(String::from),
+                    ),
+                }
+                imports.push(Import {
+                    paths,
+                    relative,
+                    bindings,
+                });
+            }
+            for cap in RUST_MOD.captures_iter(content) {
+                let mut path = module_dir.clone();
+                path.push(cap["name"].to_lowercase());
+                imports.push(Import {
+                    paths: vec![path],
+                    relative: true,
+                    bindings: vec![cap["name"].to_string()],
+                });
+            }
+        }
+        Language::Go => {
+            for cap in GO_IMPORT.captures_iter(content) {
+                // Only the package directory is on disk; the rest of the
+                // import path is the module's
+                let Some(package_dir) = cap["spec"].rsplit('/').next() else {
+                    continue;
+                };
+                let binding = match cap.name("alias").map(|a| a.as_str()) {
+                    Some("_") | Some(".") => Vec::new(),
+                    Some(alias) => vec![alias.to_string()],
+                    None => vec![package_dir.to_string()],
+                };
+                imports.push(package([package_dir].into_iter(), binding));
+            }
+        }
+        Language::Java => {
+            for cap in JAVA_IMPORT.captures_iter(content) {
+                let mut path: Vec<String> = cap["spec"].split('.').map(str::to_lowercase).collect();
+                let mut bindings = Vec::new();
+                let mut paths = Vec::new();
+                if cap.name("star").is_some() {
+                    path.push("*".to_string());
+                } else if let Some(last) = cap["spec"].rsplit('.').next() {
+                    bindings.push(last.to_string());
+                }
+                // `import static a.Env.get` names a member of the class
+                if cap.name("static").is_some() {
+                    paths.push(path[..path.len() - 1].to_vec());
+                }
+                paths.push(path);
+                imports.push(Import {
+                    paths,
+                    relative: false,
+                    bindings,
+                });
+            }
+        }
+        // C# `using` names namespaces, which do not map to files
+        Language::CSharp | Language::Custom(_) => {}
+    }
+
+    imports
+}
+
+/// An import of a path relative to `dir`
+fn relative(dir: &Path, spec: &str, bindings: Vec<String>) -> Import {
+    let mut path = components(&dir.join(spec));
+    strip_extension(&mut path);
+    Import {
+        paths: vec![path],
+        relative: true,
+        bindings,
+    }
+}
+
+/// An import of a package or module path matched by its tail
+fn package<'a>(parts: impl Iterator<Item = &'a str>, bindings: Vec<String>) -> Import {
+    let mut path: Vec<String> = parts
+        .filter(|p| !p.is_empty() && *p != ".")
+        .map(str::to_lowercase)
+        .collect();
+    strip_extension(&mut path);
+    Import {
+        paths: vec![path],
+        relative: false,
+        bindings,
+    }
+}
+
+/// Drop a spelled-out source extension and index file name, as in
+/// `./config/index.js`
+fn strip_extension(path: &mut Vec<String>) {
+    if let Some(last) = path.last_mut() {
+        if let Some((stem, ext)) = last.rsplit_once('.') {
+            if SOURCE_EXTENSIONS.contains(&ext) {
+                *last = stem.to_string();
+            }
+        }
+    }
+    strip_index(path);
+}
+
+fn strip_index(path: &mut Vec<String>) {
+    if path
+        .last()
+        .is_some_and(|l| matches!(l.as_str(), "index" | "__init__" | "mod"))
+    {
+        path.pop();
+    }
+}
+
+/// Lower-cased normal components of a path, with `.` and `..` applied
+fn components(path: &Path) -> Vec<String> {
+    let mut parts = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().to_lowercase()),
+            Component::ParentDir => {
+                parts.pop();
+            }
+            _ => {}
+        }
+    }
+    parts
+}
+
+/// Identifiers bound by a JS import clause such as `x, { a as b }`
+fn identifiers(clause: &str) -> Vec<String> {
+    IDENTIFIER
+        .find_iter(clause)
+        .map(|m| m.as_str())
+        .filter(|w| !IMPORT_KEYWORDS.contains(w))
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imported(language: Language, content: &str, from: &str, module: &str) -> bool {
+        let id = module_id(Path::new(module), language);
+        file_imports(language, content, Path::new(from))
+            .iter()
+            .any(|i| i.names(&id))
+    }
+
+    #[test]
+    fn test_javascript_specifiers() {
+        let content = "import { requireEnv as req } from '../lib/env';\nimport { env } from '$env/dynamic/private';\n";
+        assert!(imported(
+            Language::TypeScript,
+            content,
+            "src/app/db.ts",
+            "src/lib/env.ts"
+        ));
+        assert!(!imported(
+            Language::TypeScript,
+            content,
+            "src/app/db.ts",
+            "src/app/env.ts"
+        ));
+
+        let imports = file_imports(Language::TypeScript, content, Path::new("src/app/db.ts"));
+        assert_eq!(imports[0].bindings, vec!["requireEnv", "req"]);
+
+        let aliased = "const config = require('@/config/index.js');\n";
+        assert!(imported(
+            Language::JavaScript,
+            aliased,
+            "a.js",
+            "web/config/index.ts"
+        ));
+    }
+
+    #[test]
+    fn test_python_imports() {
+        let content = "from .settings import env\nfrom app import keys\nimport app.util.env as e\n";
+        let from = "app/views.py";
+        assert!(imported(Language::Python, content, from, "app/settings.py"));
+        assert!(imported(
+            Language::Python,
+            content,
+            from,
+            "src/app/keys/__init__.py"
+        ));
+        assert!(imported(Language::Python, content, from, "app/util/env.py"));
+        assert!(!imported(
+            Language::Python,
+            content,
+            from,
+            "other/settings.py"
+        ));
+    }
+
+    #[test]
+    fn test_rust_go_and_java_imports() {
+        let rust = "use crate::config::env::require_env;\nmod keys;\n";
+        assert!(imported(
+            Language::Rust,
+            rust,
+            "src/main.rs",
+            "src/config/env.rs"
+        ));
+        assert!(imported(
+            Language::Rust,
+            rust,
+            "src/main.rs",
+            "src/keys/mod.rs"
+        ));
+
+        let go = "import (\n\t\"fmt\"\n\tcfg \"github.com/acme/app/internal/config\"\n)\n";
+        assert!(imported(
+            Language::Go,
+            go,
+            "cmd/main.go",
+            "internal/config/env.go"
+        ));
+        let imports = file_imports(Language::Go, go, Path::new("cmd/main.go"));
+        assert_eq!(imports[1].bindings, vec!["cfg"]);
+
+        let java = "import com.acme.config.*;\nimport static com.acme.util.Env.get;\n";
+        assert!(imported(
+            Language::Java,
+            java,
+            "src/App.java",
+            "src/com/acme/config/Keys.java"
+        ));
+        assert!(imported(
+            Language::Java,
+            java,
+            "src/App.java",
+            "src/com/acme/util/Env.java"
+        ));
+    }
+}
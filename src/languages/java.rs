@@ -2,22 +2,152 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{expansion, LanguageScanner};
 use crate::types::{EnvVarUsage, Language};
 
-/// Scanner for Java files
-pub struct JavaScanner;
+/// Scanner for Java files and Spring configuration files
+pub struct JavaScanner {
+    /// Treat `System.getProperty` lookups as env vars
+    system_properties: bool,
+}
 
 static SYSTEM_GETENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"System\.getenv\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"System\.getenv\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static SYSTEM_GETPROPERTY: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"System\.getProperty\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"System\.getProperty\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
+
+// @Value("${...}") annotations
+static VALUE_ANNOTATION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"@Value\s*\(\s*"([^"]*)""#).unwrap());
+
+// application.properties, application-prod.yml, bootstrap.yaml, ...
+static SPRING_CONFIG_FILE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(?:application|bootstrap)(?:-[\w.-]+)?\.(?:properties|ya?ml)$"#).unwrap()
+});
+
+/// A `${name:default}` placeholder found in a string
+struct Placeholder {
+    name: String,
+    default_value: Option<String>,
+    offset: usize,
+}
 
 impl JavaScanner {
     pub fn new() -> Self {
-        Self
+        Self {
+            system_properties: false,
+        }
+    }
+
+    /// Also report `System.getProperty` lookups, for projects that mirror env into JVM properties
+    pub fn with_system_properties(mut self, enabled: bool) -> Self {
+        self.system_properties = enabled;
+        self
     }
+
+    fn is_spring_config(path: &Path) -> bool {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .map(|n| SPRING_CONFIG_FILE.is_match(n))
+            .unwrap_or(false)
+    }
+
+    /// Parse `${name}` and `${name:default}` placeholders, including nested defaults
+    fn parse_placeholders(text: &str, base: usize, out: &mut Vec<Placeholder>) {
+        let bytes = text.as_bytes();
+        let mut i = 0;
+
+        while let Some(pos) = text[i..].find("${") {
+            let start = i + pos + 2;
+            let mut depth = 1;
+            let mut end = start;
+            let mut colon = None;
+
+            while end < bytes.len() {
+                match bytes[end] {
+                    b'{' => depth += 1,
+                    b'}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    b':' if depth == 1 && colon.is_none() => colon = Some(end),
+                    _ => {}
+                }
+                end += 1;
+            }
+            if depth != 0 {
+                break;
+            }
+
+            let name_end = colon.unwrap_or(end);
+            let default_value = colon.map(|c| text[c + 1..end].to_string());
+            out.push(Placeholder {
+                name: text[start..name_end].trim().to_string(),
+                default_value: default_value.clone(),
+                offset: base + start,
+            });
+
+            // Defaults may themselves reference other placeholders
+            if let (Some(c), Some(default)) = (colon, default_value) {
+                Self::parse_placeholders(&default, base + c + 1, out);
+            }
+
+            i = end + 1;
+        }
+    }
+
+    fn placeholder_usage(
+        placeholder: Placeholder,
+        file_path: &Path,
+        line_num: usize,
+        line: &str,
+    ) -> Option<EnvVarUsage> {
+        let name = relaxed_env_name(&placeholder.name)?;
+        // Property-style keys can also be satisfied by a property file, so
+        // only env-style names are treated as required
+        let required = if placeholder.default_value.is_some() {
+            Some(false)
+        } else {
+            Some(name == placeholder.name)
+        };
+
+        Some(EnvVarUsage {
+            name,
+            file_path: file_path.to_path_buf(),
+            line: line_num,
+            column: placeholder.offset + 1,
+            language: Language::Java,
+            context: Some(line.trim().to_string()),
+            default_value: placeholder.default_value,
+            required,
+            exposure: None,
+        })
+    }
+}
+
+/// Map a Spring property name to the env var that overrides it under relaxed
+/// binding, e.g. `spring.datasource.url` -> `SPRING_DATASOURCE_URL`
+fn relaxed_env_name(property: &str) -> Option<String> {
+    let name: String = property
+        .chars()
+        .filter(|c| *c != '-')
+        .map(|c| match c {
+            '.' | '[' | ']' => '_',
+            c => c.to_ascii_uppercase(),
+        })
+        .collect();
+    let name = name.trim_end_matches('_').to_string();
+
+    let valid = name
+        .chars()
+        .next()
+        .map(|c| c.is_ascii_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some(name)
 }
 
 impl Default for JavaScanner {
@@ -32,16 +162,45 @@ impl LanguageScanner for JavaScanner {
     }
 
     fn extensions(&self) -> &'static [&'static str] {
-        &["java"]
+        &["java", "properties", "yml", "yaml"]
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("java") => true,
+            Some(_) => Self::is_spring_config(path),
+            None => false,
+        }
     }
 
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
         let mut usages = Vec::new();
-        let patterns: Vec<&Lazy<Regex>> = vec![&SYSTEM_GETENV, &SYSTEM_GETPROPERTY];
+        let spring_config = Self::is_spring_config(file_path);
+
+        let mut patterns: Vec<&Lazy<Regex>> = vec![&SYSTEM_GETENV];
+        if self.system_properties {
+            patterns.push(&SYSTEM_GETPROPERTY);
+        }
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
 
+            if spring_config {
+                let trimmed = line.trim_start();
+                if trimmed.starts_with('#') || trimmed.starts_with('!') {
+                    continue;
+                }
+
+                let mut placeholders = Vec::new();
+                Self::parse_placeholders(line, 0, &mut placeholders);
+                usages.extend(
+                    placeholders
+                        .into_iter()
+                        .filter_map(|p| Self::placeholder_usage(p, file_path, line_num, line)),
+                );
+                continue;
+            }
+
             for pattern in &patterns {
                 for cap in pattern.captures_iter(line) {
                     if let Some(m) = cap.get(1) {
@@ -52,12 +211,33 @@ impl LanguageScanner for JavaScanner {
                             column: m.start() + 1,
                             language: Language::Java,
                             context: Some(line.trim().to_string()),
+                            default_value: None,
+                            required: None,
+                            exposure: None,
                         });
                     }
                 }
             }
+
+            // @Value("${X:default}")
+            for cap in VALUE_ANNOTATION.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                let mut placeholders = Vec::new();
+                Self::parse_placeholders(m.as_str(), m.start(), &mut placeholders);
+                usages.extend(
+                    placeholders
+                        .into_iter()
+                        .filter_map(|p| Self::placeholder_usage(p, file_path, line_num, line)),
+                );
+            }
         }
 
+        usages.extend(expansion::scan_expansions(
+            Language::Java,
+            content,
+            file_path,
+        ));
+
         usages
     }
 }
@@ -77,10 +257,47 @@ mod tests {
 
     #[test]
     fn test_system_getproperty() {
-        let scanner = JavaScanner::new();
         let content = r#"String port = System.getProperty("PORT");"#;
+
+        let usages = JavaScanner::new().scan(content, Path::new("Config.java"));
+        assert!(usages.is_empty());
+
+        let scanner = JavaScanner::new().with_system_properties(true);
         let usages = scanner.scan(content, Path::new("Config.java"));
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "PORT");
     }
+
+    #[test]
+    fn test_value_annotation() {
+        let scanner = JavaScanner::new();
+        let content = r#"@Value("${API_URL:http://localhost}") private String apiUrl;"#;
+        let usages = scanner.scan(content, Path::new("Client.java"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "API_URL");
+        assert_eq!(usages[0].default_value.as_deref(), Some("http://localhost"));
+    }
+
+    #[test]
+    fn test_application_properties_placeholders() {
+        let scanner = JavaScanner::new();
+        let content = r#"
+# ${IGNORED}
+spring.datasource.url=${app.datasource.url:${DATABASE_URL}}
+server.port=${PORT:8080}
+"#;
+        let usages = scanner.scan(content, Path::new("application.properties"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["APP_DATASOURCE_URL", "DATABASE_URL", "PORT"]);
+        assert_eq!(usages[1].required, Some(true));
+        assert_eq!(usages[2].default_value.as_deref(), Some("8080"));
+    }
+
+    #[test]
+    fn test_matches_spring_config_only() {
+        let scanner = JavaScanner::new();
+        assert!(scanner.matches_file(Path::new("src/main/resources/application-prod.yml")));
+        assert!(scanner.matches_file(Path::new("App.java")));
+        assert!(!scanner.matches_file(Path::new("docker-compose.yml")));
+    }
 }
@@ -2,24 +2,35 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{frameworks, LanguageScanner};
 use crate::types::{EnvVarUsage, Language};
 
 /// Scanner for JavaScript and TypeScript files
 pub struct JavaScriptScanner;
 
-// Patterns for detecting env var usage in JS/TS
-static PROCESS_ENV_DOT: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"process\.env\.([A-Z_][A-Z0-9_]*)"#).unwrap());
+// Patterns for detecting env var usage in JS/TS. `process` also matches
+// `globalThis.process`, and `?.` optional chaining is accepted.
+static PROCESS_ENV_DOT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:process\??\.env|Bun\??\.env)\??\.([A-Za-z_][A-Za-z0-9_]*)"#).unwrap()
+});
 
-static PROCESS_ENV_BRACKET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"process\.env\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+static PROCESS_ENV_BRACKET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\b(?:process\??\.env|Bun\??\.env)(?:\?\.)?\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#)
+        .unwrap()
+});
 
 static IMPORT_META_ENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"import\.meta\.env\.([A-Z_][A-Z0-9_]*)"#).unwrap());
+    Lazy::new(|| Regex::new(r#"import\.meta\.env\.([A-Za-z_][A-Za-z0-9_]*)"#).unwrap());
+
+static DENO_ENV_GET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bDeno\??\.env\??\.get\(\s*['"`]([A-Za-z_][A-Za-z0-9_]*)['"`]\s*\)"#).unwrap()
+});
 
 static DESTRUCTURE_PROCESS_ENV: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"(?:const|let|var)\s*\{\s*([^}]+)\s*\}\s*=\s*process\.env"#).unwrap()
+    Regex::new(
+        r#"(?:const|let|var)\s*\{\s*([^}]+)\s*\}\s*=\s*(?:(?:globalThis\.)?process\.env|Bun\.env|Deno\.env\.toObject\(\s*\))"#,
+    )
+    .unwrap()
 });
 
 impl JavaScriptScanner {
@@ -34,15 +45,13 @@ impl JavaScriptScanner {
                 let s = s.trim();
                 // Handle renaming: VAR_NAME: localName
                 let name = s.split(':').next()?.trim();
-                // Only valid env var names (uppercase with underscores)
-                if name
-                    .chars()
-                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
-                    && !name.is_empty()
+                // Only valid env var names; skips `...rest` and default values
+                if !name.is_empty()
+                    && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
                     && name
                         .chars()
                         .next()
-                        .map(|c| c.is_ascii_uppercase() || c == '_')
+                        .map(|c| c.is_ascii_alphabetic() || c == '_')
                         .unwrap_or(false)
                 {
                     Some(name.to_string())
@@ -85,6 +94,9 @@ impl LanguageScanner for JavaScriptScanner {
                         column: m.start() + 1,
                         language: Language::JavaScript,
                         context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: None,
+                        exposure: None,
                     });
                 }
             }
@@ -99,6 +111,26 @@ impl LanguageScanner for JavaScriptScanner {
                         column: m.start() + 1,
                         language: Language::JavaScript,
                         context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: None,
+                        exposure: None,
+                    });
+                }
+            }
+
+            // Deno.env.get("VAR_NAME")
+            for cap in DENO_ENV_GET.captures_iter(line) {
+                if let Some(m) = cap.get(1) {
+                    usages.push(EnvVarUsage {
+                        name: m.as_str().to_string(),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num,
+                        column: m.start() + 1,
+                        language: Language::JavaScript,
+                        context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: None,
+                        exposure: None,
                     });
                 }
             }
@@ -113,6 +145,9 @@ impl LanguageScanner for JavaScriptScanner {
                         column: m.start() + 1,
                         language: Language::JavaScript,
                         context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: None,
+                        exposure: None,
                     });
                 }
             }
@@ -128,12 +163,18 @@ impl LanguageScanner for JavaScriptScanner {
                             column: m.start() + 1,
                             language: Language::JavaScript,
                             context: Some(line.trim().to_string()),
+                            default_value: None,
+                            required: None,
+                            exposure: None,
                         });
                     }
                 }
             }
         }
 
+        // SvelteKit $env/*, Astro astro:env/* and Nuxt useRuntimeConfig()
+        usages.extend(frameworks::scan_env_modules(content, file_path));
+
         usages
     }
 }
@@ -181,6 +222,50 @@ mod tests {
         assert!(names.contains(&"DATABASE_URL"));
     }
 
+    #[test]
+    fn test_lowercase_names() {
+        let scanner = JavaScriptScanner::new();
+        let content = r#"
+const cache = process.env.npm_config_cache;
+const { http_proxy, ...rest } = process.env;
+"#;
+        let usages = scanner.scan(content, Path::new("test.js"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["npm_config_cache", "http_proxy"]);
+    }
+
+    #[test]
+    fn test_deno_and_bun() {
+        let scanner = JavaScriptScanner::new();
+        let content = r#"
+const port = Deno.env.get("PORT");
+const { DENO_KV_PATH } = Deno.env.toObject();
+const token = Bun.env.BUN_TOKEN;
+const region = Bun.env["AWS_REGION"];
+"#;
+        let usages = scanner.scan(content, Path::new("main.ts"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["PORT", "DENO_KV_PATH", "BUN_TOKEN", "AWS_REGION"]
+        );
+        assert_eq!(usages[0].column, 28);
+    }
+
+    #[test]
+    fn test_global_this_and_optional_chaining() {
+        let scanner = JavaScriptScanner::new();
+        let content = r#"
+const a = globalThis.process.env.API_URL;
+const b = process?.env?.LOG_LEVEL;
+const c = globalThis.process?.env?.["NODE_ENV"];
+const { SHARD } = globalThis.process.env;
+"#;
+        let usages = scanner.scan(content, Path::new("edge.js"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["API_URL", "LOG_LEVEL", "NODE_ENV", "SHARD"]);
+    }
+
     #[test]
     fn test_multiple_usages() {
         let scanner = JavaScriptScanner::new();
@@ -1,16 +1,48 @@
+#[cfg(feature = "ast")]
+mod ast;
+mod constants;
+mod container;
+mod context;
 mod csharp;
+mod custom;
+mod expansion;
+mod frameworks;
 mod go;
+mod imports;
 mod java;
 mod javascript;
 mod php;
 mod python;
 mod ruby;
 mod rust_lang;
+mod template;
+mod workers;
+mod wrapper;
 
+use std::collections::HashMap;
 use std::path::Path;
 
+use crate::config::ScanConfig;
 use crate::types::{EnvVarUsage, Language};
 
+#[cfg(feature = "ast")]
+pub use ast::AstScanner;
+pub use constants::{file_constants, resolve_key, scan_constant_keys};
+pub use container::ContainerScanner;
+pub use context::{GoNestedField, ScanContext};
+pub use csharp::CSharpScanner;
+pub use custom::PatternScanner;
+pub use go::GoScanner;
+pub use java::JavaScanner;
+pub use javascript::JavaScriptScanner;
+pub use php::PhpScanner;
+pub use python::PythonScanner;
+pub use ruby::RubyScanner;
+pub use rust_lang::RustScanner;
+pub use template::TemplateScanner;
+pub use workers::WorkersScanner;
+pub use wrapper::{collect_wrappers, scan_wrapper_calls, EnvWrapper};
+
 /// Trait for language-specific env var scanning
 pub trait LanguageScanner: Send + Sync {
     /// Returns the language this scanner handles
@@ -19,42 +51,168 @@ pub trait LanguageScanner: Send + Sync {
     /// Returns file extensions this scanner handles
     fn extensions(&self) -> &'static [&'static str];
 
+    /// Returns true if this scanner should scan the given file
+    fn matches_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| self.extensions().contains(&e.to_lowercase().as_str()))
+            .unwrap_or(false)
+    }
+
     /// Scan content for env var usages
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage>;
+
+    /// Record project-wide facts from a file before any file is scanned
+    fn collect_context(&self, _content: &str, _file_path: &Path, _context: &mut ScanContext) {}
+
+    /// Scan content for env var usages, using facts collected from the whole project
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        _context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
+        self.scan(content, file_path)
+    }
 }
 
 /// Registry of all language scanners
+///
+/// Library users can start from the built-in scanners or an empty registry,
+/// then register their own `LanguageScanner` implementations:
+///
+/// ```no_run
+/// use env_audit::languages::{LanguageRegistry, PythonScanner};
+/// use env_audit::types::Language;
+///
+/// let mut registry = LanguageRegistry::empty();
+/// registry.register(Box::new(PythonScanner::new()));
+/// registry.map_extension("pyw", Language::Python);
+/// ```
 pub struct LanguageRegistry {
     scanners: Vec<Box<dyn LanguageScanner>>,
+    /// Extension -> language whose scanners handle it, overriding `matches_file`
+    extension_overrides: HashMap<String, Language>,
 }
 
 impl LanguageRegistry {
     pub fn new() -> Self {
+        Self::from_config(&ScanConfig::default())
+    }
+
+    /// Create a registry with no scanners
+    pub fn empty() -> Self {
         Self {
-            scanners: vec![
-                Box::new(javascript::JavaScriptScanner::new()),
-                Box::new(python::PythonScanner::new()),
-                Box::new(rust_lang::RustScanner::new()),
-                Box::new(go::GoScanner::new()),
-                Box::new(ruby::RubyScanner::new()),
-                Box::new(php::PhpScanner::new()),
-                Box::new(java::JavaScanner::new()),
-                Box::new(csharp::CSharpScanner::new()),
-            ],
+            scanners: Vec::new(),
+            extension_overrides: HashMap::new(),
         }
     }
 
-    /// Get the appropriate scanner for a file based on its extension
-    pub fn get_scanner_for_file(&self, path: &Path) -> Option<&dyn LanguageScanner> {
-        let ext = path.extension()?.to_str()?.to_lowercase();
+    /// Add a scanner. Scanners registered later run alongside earlier ones
+    /// for files they both match.
+    pub fn register(&mut self, scanner: Box<dyn LanguageScanner>) -> &mut Self {
+        self.scanners.push(scanner);
+        self
+    }
+
+    /// Remove every scanner for a language, including built-ins
+    pub fn remove(&mut self, language: Language) -> &mut Self {
+        self.scanners.retain(|s| s.language() != language);
+        self
+    }
+
+    /// Route files with this extension to the scanners of `language` only
+    pub fn map_extension(&mut self, extension: &str, language: Language) -> &mut Self {
+        self.extension_overrides
+            .insert(extension.trim_start_matches('.').to_lowercase(), language);
+        self
+    }
 
+    /// Languages with at least one registered scanner
+    pub fn languages(&self) -> Vec<Language> {
+        let mut languages: Vec<Language> = Vec::new();
         for scanner in &self.scanners {
-            if scanner.extensions().contains(&ext.as_str()) {
-                return Some(scanner.as_ref());
+            if !languages.contains(&scanner.language()) {
+                languages.push(scanner.language());
             }
         }
+        languages
+    }
 
-        None
+    /// Returns true if any scanner handles this file
+    pub fn supports_file(&self, path: &Path) -> bool {
+        !self.get_scanners_for_file(path).is_empty()
+    }
+
+    /// Build the registry with scanner options taken from the scan config
+    pub fn from_config(config: &ScanConfig) -> Self {
+        let mut scanners: Vec<Box<dyn LanguageScanner>> = vec![
+            Box::new(javascript::JavaScriptScanner::new()),
+            Box::new(container::ContainerScanner::new()),
+            Box::new(python::PythonScanner::new()),
+            Box::new(rust_lang::RustScanner::new()),
+            Box::new(go::GoScanner::new()),
+            Box::new(ruby::RubyScanner::new()),
+            Box::new(php::PhpScanner::new()),
+            Box::new(
+                java::JavaScanner::new().with_system_properties(config.java_system_properties),
+            ),
+            Box::new(csharp::CSharpScanner::new()),
+        ];
+
+        // parser = "ast" swaps regex matching of direct env reads for syntax trees
+        #[cfg(feature = "ast")]
+        if config.parser == "ast" {
+            scanners = scanners
+                .into_iter()
+                .map(|s| Box::new(ast::AstScanner::new(s)) as Box<dyn LanguageScanner>)
+                .collect();
+        }
+
+        // Cloudflare Workers read bindings from the `env` handler argument
+        if config.workers {
+            scanners.push(Box::new(workers::WorkersScanner::new()));
+        }
+
+        // User-defined patterns from [[scan.patterns]]
+        for pattern in &config.patterns {
+            if let Some(scanner) = PatternScanner::from_config(pattern) {
+                scanners.push(Box::new(scanner));
+            }
+        }
+
+        // Template placeholders from template_presets and [[scan.templates]]
+        for scanner in TemplateScanner::from_scan_config(config) {
+            scanners.push(Box::new(scanner));
+        }
+
+        Self {
+            scanners,
+            extension_overrides: HashMap::new(),
+        }
+    }
+
+    /// Get the appropriate scanner for a file based on its extension
+    pub fn get_scanner_for_file(&self, path: &Path) -> Option<&dyn LanguageScanner> {
+        self.get_scanners_for_file(path).into_iter().next()
+    }
+
+    /// Get every scanner that handles a file. Shared config formats such as
+    /// YAML can be relevant to more than one scanner.
+    pub fn get_scanners_for_file(&self, path: &Path) -> Vec<&dyn LanguageScanner> {
+        let mapped = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .and_then(|e| self.extension_overrides.get(&e.to_lowercase()));
+
+        self.scanners
+            .iter()
+            .filter(|s| match mapped {
+                Some(language) => s.language() == *language,
+                None => s.matches_file(path),
+            })
+            .map(|s| s.as_ref())
+            .collect()
     }
 }
 
@@ -63,3 +221,77 @@ impl Default for LanguageRegistry {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct ElixirScanner;
+
+    impl LanguageScanner for ElixirScanner {
+        fn language(&self) -> Language {
+            Language::Custom("elixir")
+        }
+
+        fn extensions(&self) -> &'static [&'static str] {
+            &["ex", "exs"]
+        }
+
+        fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+            content
+                .match_indices("System.get_env(\"PORT\")")
+                .map(|(i, _)| EnvVarUsage {
+                    name: "PORT".to_string(),
+                    file_path: file_path.to_path_buf(),
+                    line: 1,
+                    column: i + 1,
+                    language: self.language(),
+                    context: None,
+                    default_value: None,
+                    required: None,
+                    exposure: None,
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_register_custom_scanner() {
+        let mut registry = LanguageRegistry::new();
+        registry.register(Box::new(ElixirScanner));
+
+        let scanner = registry
+            .get_scanner_for_file(Path::new("lib/app.ex"))
+            .unwrap();
+        assert_eq!(scanner.language(), Language::Custom("elixir"));
+        let usages = scanner.scan(r#"System.get_env("PORT")"#, Path::new("lib/app.ex"));
+        assert_eq!(usages[0].language.id(), "elixir");
+    }
+
+    #[test]
+    fn test_remove_builtin() {
+        let mut registry = LanguageRegistry::new();
+        registry.remove(Language::Python);
+        assert!(!registry.supports_file(Path::new("app.py")));
+        assert!(registry.supports_file(Path::new("app.rb")));
+    }
+
+    #[test]
+    fn test_container_files_supported() {
+        let registry = LanguageRegistry::new();
+        for file in ["App.vue", "Page.svelte", "index.astro", "index.html"] {
+            let scanners = registry.get_scanners_for_file(Path::new(file));
+            assert_eq!(scanners.len(), 1, "{}", file);
+            assert_eq!(scanners[0].language(), Language::JavaScript, "{}", file);
+        }
+    }
+
+    #[test]
+    fn test_map_extension() {
+        let mut registry = LanguageRegistry::new();
+        registry.map_extension(".pyw", Language::Python);
+        let scanners = registry.get_scanners_for_file(Path::new("gui.pyw"));
+        assert_eq!(scanners.len(), 1);
+        assert_eq!(scanners[0].language(), Language::Python);
+    }
+}
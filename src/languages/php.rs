@@ -1,30 +1,131 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::wrapper::call_arguments;
+use super::{LanguageScanner, ScanContext};
 use crate::types::{EnvVarUsage, Language};
 
-/// Scanner for PHP files
+/// Scanner for PHP files and Symfony YAML config
+///
+/// Laravel reads env only inside `config/*.php`; the rest of the app calls
+/// `config('file.key')`. Those config keys are mapped back to the env vars
+/// that feed them so `config()` call sites count as usages.
 pub struct PhpScanner;
 
 static GETENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"getenv\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"getenv\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap());
 
 static DOLLAR_ENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\$_ENV\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\$_ENV\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#).unwrap());
 
 static DOLLAR_SERVER: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\$_SERVER\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\$_SERVER\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#).unwrap());
 
-// Laravel env() helper
+// Laravel env() helper; a comma starts the default, which may span lines
+// and contain calls of its own
 static LARAVEL_ENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\benv\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\benv\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]\s*(,)?"#).unwrap());
+
+// config('database.url') / Config::get('database.url')
+static LARAVEL_CONFIG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?:\bconfig|Config::get)\s*\(\s*['"]([\w.-]+)['"]"#).unwrap());
+
+// 'key' => [ ... or 'key' => env('X') in config/*.php
+static CONFIG_ARRAY_KEY: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"['"]([\w.-]+)['"]\s*=>\s*(.*)$"#).unwrap());
+
+// Symfony %env(X)% with optional processors, e.g. %env(int:PORT)%
+static SYMFONY_ENV: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"%env\(((?:[\w-]+:)*)([A-Za-z_][A-Za-z0-9_]*)\)%"#).unwrap());
+
+// Symfony parameter defaults: env(X): 'value'
+static SYMFONY_ENV_DEFAULT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*['"]?env\(([A-Za-z_][A-Za-z0-9_]*)\)['"]?\s*:\s*['"]?([^'"]*)['"]?"#).unwrap()
+});
 
 impl PhpScanner {
     pub fn new() -> Self {
         Self
     }
+
+    fn is_yaml(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yml") | Some("yaml")
+        )
+    }
+
+    /// Laravel config files live directly in `config/`
+    fn laravel_config_name(path: &Path) -> Option<&str> {
+        if path.extension()?.to_str()? != "php" {
+            return None;
+        }
+        if path.parent()?.file_name()?.to_str()? != "config" {
+            return None;
+        }
+        path.file_stem()?.to_str()
+    }
+
+    /// Config keys of the Laravel app a file belongs to: the nearest app
+    /// root above it, or the only app in the project
+    fn config_env<'a>(
+        context: &'a ScanContext,
+        file_path: &Path,
+    ) -> Option<&'a HashMap<String, String>> {
+        file_path
+            .ancestors()
+            .skip(1)
+            .find_map(|dir| context.php_config_env.get(dir))
+            .or_else(|| match context.php_config_env.len() {
+                1 => context.php_config_env.values().next(),
+                _ => None,
+            })
+    }
+
+    fn unquote(value: &str) -> String {
+        value
+            .trim()
+            .trim_matches(|c| c == '"' || c == '\'')
+            .to_string()
+    }
+
+    /// Usages from Symfony `%env()%` references
+    fn scan_symfony(content: &str, file_path: &Path, usages: &mut Vec<EnvVarUsage>) {
+        let defaults: std::collections::HashMap<String, String> = content
+            .lines()
+            .filter_map(|line| SYMFONY_ENV_DEFAULT.captures(line))
+            .map(|cap| (cap[1].to_string(), cap[2].to_string()))
+            .collect();
+
+        for (line_num, line) in content.lines().enumerate() {
+            if line.trim_start().starts_with('#') {
+                continue;
+            }
+
+            for cap in SYMFONY_ENV.captures_iter(line) {
+                let m = cap.get(2).unwrap();
+                let processors = cap.get(1).map(|p| p.as_str()).unwrap_or("");
+                let default_value = defaults.get(m.as_str()).cloned();
+                // The default: processor falls back to a parameter when unset
+                let required =
+                    !processors.split(':').any(|p| p == "default") && default_value.is_none();
+
+                usages.push(EnvVarUsage {
+                    name: m.as_str().to_string(),
+                    file_path: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    column: m.start() + 1,
+                    language: Language::Php,
+                    context: Some(line.trim().to_string()),
+                    default_value,
+                    required: Some(required),
+                    exposure: None,
+                });
+            }
+        }
+    }
 }
 
 impl Default for PhpScanner {
@@ -39,12 +140,71 @@ impl LanguageScanner for PhpScanner {
     }
 
     fn extensions(&self) -> &'static [&'static str] {
-        &["php"]
+        &["php", "yaml", "yml"]
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("php") => true,
+            // Symfony config lives under config/ (packages/, services.yaml, ...)
+            Some("yaml") | Some("yml") => path
+                .ancestors()
+                .skip(1)
+                .any(|p| p.file_name().and_then(|n| n.to_str()) == Some("config")),
+            _ => false,
+        }
     }
 
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        self.scan_with_context(content, file_path, &ScanContext::default())
+    }
+
+    fn collect_context(&self, content: &str, file_path: &Path, context: &mut ScanContext) {
+        let Some(config_name) = Self::laravel_config_name(file_path) else {
+            return;
+        };
+        // The app root holds `config/`
+        let root = file_path
+            .parent()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let config_env = context.php_config_env.entry(root).or_default();
+
+        // Track the nested array keys leading to each env() call
+        let mut path: Vec<String> = vec![config_name.to_string()];
+        for line in content.lines() {
+            let trimmed = line.trim();
+
+            if let Some(cap) = CONFIG_ARRAY_KEY.captures(trimmed) {
+                let value = cap[2].trim();
+                if value.starts_with('[') && !value.contains(']') {
+                    path.push(cap[1].to_string());
+                } else if let Some(env) = LARAVEL_ENV.captures(value) {
+                    let key = format!("{}.{}", path.join("."), &cap[1]);
+                    config_env.insert(key, env[1].to_string());
+                }
+            } else if trimmed.starts_with(']') && path.len() > 1 {
+                path.pop();
+            }
+        }
+    }
+
+    fn scan_with_context(
+        &self,
+        content: &str,
+        file_path: &Path,
+        context: &ScanContext,
+    ) -> Vec<EnvVarUsage> {
         let mut usages = Vec::new();
-        let patterns: Vec<&Lazy<Regex>> = vec![&GETENV, &DOLLAR_ENV, &DOLLAR_SERVER, &LARAVEL_ENV];
+
+        if Self::is_yaml(file_path) {
+            Self::scan_symfony(content, file_path, &mut usages);
+            return usages;
+        }
+
+        let patterns: Vec<&Lazy<Regex>> = vec![&GETENV, &DOLLAR_ENV, &DOLLAR_SERVER];
+        let config_env = Self::config_env(context, file_path);
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
@@ -59,10 +219,73 @@ impl LanguageScanner for PhpScanner {
                             column: m.start() + 1,
                             language: Language::Php,
                             context: Some(line.trim().to_string()),
+                            default_value: None,
+                            required: None,
+                            exposure: None,
                         });
                     }
                 }
             }
+
+            // Indirect usages through config('file.key'); a key naming a
+            // whole section reads every env var beneath it
+            for cap in LARAVEL_CONFIG.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                let key = m.as_str();
+                let section = format!("{}.", key);
+
+                let mut vars: Vec<&String> = config_env
+                    .into_iter()
+                    .flatten()
+                    .filter(|(k, _)| k.as_str() == key || k.starts_with(&section))
+                    .map(|(_, v)| v)
+                    .collect();
+                vars.sort();
+                vars.dedup();
+
+                for var in vars {
+                    usages.push(EnvVarUsage {
+                        name: var.clone(),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num,
+                        column: m.start() + 1,
+                        language: Language::Php,
+                        context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: None,
+                        exposure: None,
+                    });
+                }
+            }
+        }
+
+        // env() calls, whose default may continue on the following lines
+        let lines: Vec<&str> = content.lines().collect();
+        for cap in LARAVEL_ENV.captures_iter(content) {
+            let m = cap.get(1).unwrap();
+            let default_value = cap
+                .get(2)
+                .and_then(|comma| call_arguments(content, comma.end(), true).first().copied())
+                .map(|(_, d)| Self::unquote(d))
+                .filter(|d| !d.is_empty());
+            let line = content[..m.start()].matches('\n').count();
+            let line_start = content[..m.start()].rfind('\n').map_or(0, |i| i + 1);
+            usages.push(EnvVarUsage {
+                name: m.as_str().to_string(),
+                file_path: file_path.to_path_buf(),
+                line: line + 1,
+                column: m.start() - line_start + 1,
+                language: Language::Php,
+                context: lines.get(line).map(|l| l.trim().to_string()),
+                default_value,
+                required: None,
+                exposure: None,
+            });
+        }
+
+        // %env(X)% can also appear in PHP-based Symfony config
+        if content.contains("%env(") {
+            Self::scan_symfony(content, file_path, &mut usages);
         }
 
         usages
@@ -98,5 +321,108 @@ mod tests {
         let usages = scanner.scan(content, Path::new("config/app.php"));
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "APP_DEBUG");
+        assert_eq!(usages[0].default_value.as_deref(), Some("false"));
+    }
+
+    #[test]
+    fn test_laravel_env_call_defaults() {
+        let scanner = PhpScanner::new();
+        let content = r#"<?php
+return [
+    'root' => env('STORAGE_ROOT', storage_path('app')),
+    'key' => env(
+        'APP_KEY',
+        'base64:abc'
+    ),
+    'url' => env('APP_URL'),
+];
+"#;
+        let usages = scanner.scan(content, Path::new("config/filesystems.php"));
+        let found: Vec<_> = usages
+            .iter()
+            .map(|u| (u.name.as_str(), u.line, u.default_value.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("STORAGE_ROOT", 3, Some("storage_path('app')")),
+                ("APP_KEY", 5, Some("base64:abc")),
+                ("APP_URL", 8, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_laravel_config_mapping() {
+        let scanner = PhpScanner::new();
+        let config = r#"<?php
+return [
+    'default' => env('DB_CONNECTION', 'mysql'),
+    'connections' => [
+        'pgsql' => [
+            'url' => env('DATABASE_URL'),
+        ],
+    ],
+];
+"#;
+        let mut context = ScanContext::default();
+        scanner.collect_context(config, Path::new("config/database.php"), &mut context);
+        assert_eq!(
+            context.php_config_env[Path::new("")].get("database.connections.pgsql.url"),
+            Some(&"DATABASE_URL".to_string())
+        );
+
+        let code = r#"$url = config('database.connections.pgsql.url');
+$conn = Config::get('database.default');"#;
+        let usages = scanner.scan_with_context(code, Path::new("app/Db.php"), &context);
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["DATABASE_URL", "DB_CONNECTION"]);
+    }
+
+    #[test]
+    fn test_laravel_config_per_app() {
+        let scanner = PhpScanner::new();
+        let mut context = ScanContext::default();
+        let api = "<?php\nreturn [\n    'url' => env('API_DATABASE_URL'),\n];\n";
+        let admin = "<?php\nreturn [\n    'url' => env('ADMIN_DATABASE_URL'),\n];\n";
+        scanner.collect_context(api, Path::new("apps/api/config/database.php"), &mut context);
+        scanner.collect_context(
+            admin,
+            Path::new("apps/admin/config/database.php"),
+            &mut context,
+        );
+
+        let code = "$url = config('database.url');";
+        let names = |file: &str| -> Vec<String> {
+            scanner
+                .scan_with_context(code, Path::new(file), &context)
+                .into_iter()
+                .map(|u| u.name)
+                .collect()
+        };
+        assert_eq!(names("apps/api/app/Db.php"), vec!["API_DATABASE_URL"]);
+        assert_eq!(names("apps/admin/app/Db.php"), vec!["ADMIN_DATABASE_URL"]);
+        // Outside both apps, the key is ambiguous
+        assert!(names("packages/shared/Db.php").is_empty());
+    }
+
+    #[test]
+    fn test_symfony_env_processors() {
+        let scanner = PhpScanner::new();
+        let content = r#"
+parameters:
+    env(MAILER_PORT): '25'
+    app.port: '%env(int:MAILER_PORT)%'
+    app.secret: '%env(APP_SECRET)%'
+    app.dsn: '%env(default:fallback_dsn:SENTRY_DSN)%'
+"#;
+        let path = Path::new("config/services.yaml");
+        assert!(scanner.matches_file(path));
+        let usages = scanner.scan(content, path);
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["MAILER_PORT", "APP_SECRET", "SENTRY_DSN"]);
+        assert_eq!(usages[0].default_value.as_deref(), Some("25"));
+        assert_eq!(usages[1].required, Some(true));
+        assert_eq!(usages[2].required, Some(false));
     }
 }
@@ -2,7 +2,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{expansion, LanguageScanner};
 use crate::types::{EnvVarUsage, Language};
 
 /// Scanner for Python files
@@ -10,24 +10,25 @@ pub struct PythonScanner;
 
 // Patterns for detecting env var usage in Python
 static OS_ENVIRON_BRACKET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.environ\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"os\.environ\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#).unwrap());
 
-static OS_ENVIRON_GET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.environ\.get\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+static OS_ENVIRON_GET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"os\.environ\.get\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap()
+});
 
 static OS_GETENV: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"os\.getenv\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"os\.getenv\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap());
 
 // When `from os import environ` is used
 static ENVIRON_BRACKET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\benviron\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\benviron\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#).unwrap());
 
 static ENVIRON_GET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\benviron\.get\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\benviron\.get\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap());
 
 // When `from os import getenv` is used
 static GETENV_DIRECT: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"\bgetenv\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"\bgetenv\s*\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap());
 
 impl PythonScanner {
     pub fn new() -> Self {
@@ -78,6 +79,9 @@ impl LanguageScanner for PythonScanner {
                                 column: m.start() + 1,
                                 language: Language::Python,
                                 context: Some(line.trim().to_string()),
+                                default_value: None,
+                                required: None,
+                                exposure: None,
                             });
                         }
                     }
@@ -85,6 +89,12 @@ impl LanguageScanner for PythonScanner {
             }
         }
 
+        usages.extend(expansion::scan_expansions(
+            Language::Python,
+            content,
+            file_path,
+        ));
+
         usages
     }
 }
@@ -143,4 +153,13 @@ debug = getenv('DEBUG')
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "DEBUG");
     }
+
+    #[test]
+    fn test_lowercase_name() {
+        let scanner = PythonScanner::new();
+        let content = r#"proxy = os.environ["http_proxy"]"#;
+        let usages = scanner.scan(content, Path::new("test.py"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "http_proxy");
+    }
 }
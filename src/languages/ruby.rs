@@ -5,19 +5,48 @@ use std::path::Path;
 use super::LanguageScanner;
 use crate::types::{EnvVarUsage, Language};
 
-/// Scanner for Ruby files
+/// Scanner for Ruby files, ERB templates and YAML config with embedded ERB
 pub struct RubyScanner;
 
 static ENV_BRACKET: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"ENV\[['"]([A-Z_][A-Z0-9_]*)['"]\]"#).unwrap());
+    Lazy::new(|| Regex::new(r#"ENV\[['"]([A-Za-z_][A-Za-z0-9_]*)['"]\]"#).unwrap());
 
-static ENV_FETCH: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"ENV\.fetch\s*\(\s*['"]([A-Z_][A-Z0-9_]*)['"]"#).unwrap());
+// ENV.fetch("X"), ENV.fetch("X", default), ENV.fetch("X") { default }
+static ENV_FETCH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"ENV\.fetch\s*\(?\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]\s*(?:,\s*([^)]*?)\s*\)|\)?\s*(\{))?"#,
+    )
+    .unwrap()
+});
+
+// ENV.key?("X"), ENV.include?, ENV.has_key?, ENV.member?
+static ENV_KEY_CHECK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"ENV\.(?:key|include|has_key|member)\?\s*\(?\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#)
+        .unwrap()
+});
+
+// Dotenv.require_keys("X", "Y") / Figaro.require_keys("X")
+static REQUIRE_KEYS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?:Dotenv|Figaro)\.require_keys\s*\(?([^)\n]*)"#).unwrap());
+
+static QUOTED_NAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#).unwrap());
+
+// Figaro.env.api_key maps to API_KEY; the bang form raises when unset
+static FIGARO_ENV: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"Figaro\.env\.([a-z_][a-z0-9_]*)(!|\?)?"#).unwrap());
 
 impl RubyScanner {
     pub fn new() -> Self {
         Self
     }
+
+    fn is_yaml(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("yml") | Some("yaml")
+        )
+    }
 }
 
 impl Default for RubyScanner {
@@ -32,30 +61,90 @@ impl LanguageScanner for RubyScanner {
     }
 
     fn extensions(&self) -> &'static [&'static str] {
-        &["rb"]
+        &["rb", "erb", "yml", "yaml"]
     }
 
     fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
         let mut usages = Vec::new();
-        let patterns: Vec<&Lazy<Regex>> = vec![&ENV_BRACKET, &ENV_FETCH];
+
+        // Plain YAML cannot read ENV; only ERB-templated YAML (config/database.yml) can
+        if Self::is_yaml(file_path) && !content.contains("<%") {
+            return usages;
+        }
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
+            let mut push =
+                |m: regex::Match, default_value: Option<String>, required: Option<bool>| {
+                    usages.push(EnvVarUsage {
+                        name: m.as_str().to_string(),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num,
+                        column: m.start() + 1,
+                        language: Language::Ruby,
+                        context: Some(line.trim().to_string()),
+                        default_value,
+                        required,
+                        exposure: None,
+                    });
+                };
+
+            for cap in ENV_BRACKET.captures_iter(line) {
+                push(cap.get(1).unwrap(), None, None);
+            }
 
-            for pattern in &patterns {
-                for cap in pattern.captures_iter(line) {
-                    if let Some(m) = cap.get(1) {
-                        usages.push(EnvVarUsage {
-                            name: m.as_str().to_string(),
-                            file_path: file_path.to_path_buf(),
-                            line: line_num,
-                            column: m.start() + 1,
-                            language: Language::Ruby,
-                            context: Some(line.trim().to_string()),
-                        });
-                    }
+            for cap in ENV_FETCH.captures_iter(line) {
+                let default_value = cap.get(2).map(|d| {
+                    d.as_str()
+                        .trim_matches(|c| c == '"' || c == '\'')
+                        .to_string()
+                });
+                // Without a fallback, fetch raises KeyError
+                let required = default_value.is_none() && cap.get(3).is_none();
+                push(cap.get(1).unwrap(), default_value, Some(required));
+            }
+
+            for cap in ENV_KEY_CHECK.captures_iter(line) {
+                push(cap.get(1).unwrap(), None, Some(false));
+            }
+
+            for cap in REQUIRE_KEYS.captures_iter(line) {
+                let args = cap.get(1).unwrap();
+                for name in QUOTED_NAME.captures_iter(args.as_str()) {
+                    let m = name.get(1).unwrap();
+                    usages.push(EnvVarUsage {
+                        name: m.as_str().to_string(),
+                        file_path: file_path.to_path_buf(),
+                        line: line_num,
+                        column: args.start() + m.start() + 1,
+                        language: Language::Ruby,
+                        context: Some(line.trim().to_string()),
+                        default_value: None,
+                        required: Some(true),
+                        exposure: None,
+                    });
                 }
             }
+
+            for cap in FIGARO_ENV.captures_iter(line) {
+                let m = cap.get(1).unwrap();
+                let required = match cap.get(2).map(|s| s.as_str()) {
+                    Some("!") => Some(true),
+                    Some(_) => Some(false),
+                    None => None,
+                };
+                usages.push(EnvVarUsage {
+                    name: m.as_str().to_uppercase(),
+                    file_path: file_path.to_path_buf(),
+                    line: line_num,
+                    column: m.start() + 1,
+                    language: Language::Ruby,
+                    context: Some(line.trim().to_string()),
+                    default_value: None,
+                    required,
+                    exposure: None,
+                });
+            }
         }
 
         usages
@@ -83,4 +172,64 @@ mod tests {
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "PORT");
     }
+
+    #[test]
+    fn test_env_fetch_default() {
+        let scanner = RubyScanner::new();
+        let content = r#"
+host = ENV.fetch("REDIS_HOST", "localhost")
+key = ENV.fetch("SECRET_KEY_BASE")
+pool = ENV.fetch("DB_POOL") { 5 }
+"#;
+        let usages = scanner.scan(content, Path::new("config.rb"));
+        assert_eq!(usages.len(), 3);
+        assert_eq!(usages[0].default_value.as_deref(), Some("localhost"));
+        assert_eq!(usages[1].required, Some(true));
+        assert!(usages[2].is_optional());
+    }
+
+    #[test]
+    fn test_env_key_checks() {
+        let scanner = RubyScanner::new();
+        let content = r#"enable! if ENV.key?("FEATURE_X") || ENV.include?('FEATURE_Y')"#;
+        let usages = scanner.scan(content, Path::new("boot.rb"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(names, vec!["FEATURE_X", "FEATURE_Y"]);
+    }
+
+    #[test]
+    fn test_dotenv_require_keys() {
+        let scanner = RubyScanner::new();
+        let content = r#"Dotenv.require_keys("SERVICE_APP_ID", "SERVICE_KEY")"#;
+        let usages = scanner.scan(content, Path::new("application.rb"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[1].name, "SERVICE_KEY");
+        assert_eq!(usages[1].required, Some(true));
+    }
+
+    #[test]
+    fn test_figaro_env() {
+        let scanner = RubyScanner::new();
+        let content = r#"Stripe.api_key = Figaro.env.stripe_api_key!"#;
+        let usages = scanner.scan(content, Path::new("stripe.rb"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "STRIPE_API_KEY");
+        assert_eq!(usages[0].required, Some(true));
+    }
+
+    #[test]
+    fn test_erb_in_yaml() {
+        let scanner = RubyScanner::new();
+        let content = r#"
+production:
+  url: <%= ENV["DATABASE_URL"] %>
+  pool: <%= ENV.fetch("RAILS_MAX_THREADS") { 5 } %>
+"#;
+        let usages = scanner.scan(content, Path::new("config/database.yml"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "DATABASE_URL");
+
+        let plain = r#"name: ENV["NOT_ERB"]"#;
+        assert!(scanner.scan(plain, Path::new("ci.yml")).is_empty());
+    }
 }
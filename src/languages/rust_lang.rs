@@ -1,29 +1,284 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use std::collections::HashMap;
 use std::path::Path;
 
-use super::LanguageScanner;
+use super::{expansion, LanguageScanner};
 use crate::types::{EnvVarUsage, Language};
 
 /// Scanner for Rust files
 pub struct RustScanner;
 
 static ENV_VAR: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"(?:std::)?env::var\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"(?:std::)?env::var\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static ENV_VAR_OS: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"(?:std::)?env::var_os\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"(?:std::)?env::var_os\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static ENV_MACRO: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"env!\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"env!\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
 
 static OPTION_ENV_MACRO: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"option_env!\s*\(\s*"([A-Z_][A-Z0-9_]*)""#).unwrap());
+    Lazy::new(|| Regex::new(r#"option_env!\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
+
+// dotenvy::var / dotenv::var
+static DOTENVY_VAR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"dotenvy?::var\s*\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
+
+// Struct definitions whose fields may be bound to env vars
+static STRUCT_DEF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"^\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+(\w+)"#).unwrap());
+
+static STRUCT_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^\s*(?:pub(?:\([^)]*\))?\s+)?([a-z_][a-z0-9_]*)\s*:\s*(.+?)\s*,?\s*(?://.*)?$"#)
+        .unwrap()
+});
+
+static SERDE_RENAME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"serde\s*\([^)]*\brename\s*=\s*"([^"]+)""#).unwrap());
+
+static SERDE_RENAME_ALL: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"serde\s*\([^)]*\brename_all\s*=\s*"([^"]+)""#).unwrap());
+
+static SERDE_DEFAULT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"serde\s*\([^)]*\bdefault\b"#).unwrap());
+
+// #[arg(long, env = "X")] / #[clap(env)]
+static CLAP_ATTR: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"#\[\s*(?:arg|clap)\s*\((.*)\)\s*\]"#).unwrap());
+
+static CLAP_ENV_NAMED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\benv\s*=\s*"([A-Za-z_][A-Za-z0-9_]*)""#).unwrap());
+
+static CLAP_ENV_BARE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?:^|[(,\s])env\s*(?:,|$)"#).unwrap());
+
+static CLAP_DEFAULT: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bdefault_value(?:_t)?\s*=\s*"?([^",)]*)"?"#).unwrap());
+
+// envy::from_env::<T>() / envy::prefixed("P").from_env::<T>()
+static ENVY_TURBOFISH: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"envy::(?:prefixed\s*\(\s*"([^"]*)"\s*\)\s*\.)?from_env\s*::\s*<\s*(\w+)\s*>"#)
+        .unwrap()
+});
+
+// let cfg: T = envy::from_env()
+static ENVY_ANNOTATED: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"let\s+\w+\s*:\s*(\w+)\s*=\s*envy::(?:prefixed\s*\(\s*"([^"]*)"\s*\)\s*\.)?from_env\s*\("#,
+    )
+    .unwrap()
+});
+
+// config::Environment::with_prefix("APP") combined with try_deserialize::<T>()
+static CONFIG_ENV_PREFIX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"Environment::with_prefix\s*\(\s*"([^"]*)""#).unwrap());
+
+static CONFIG_DESERIALIZE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"try_deserialize\s*::\s*<\s*(\w+)\s*>|let\s+\w+\s*:\s*(\w+)\s*=.*try_deserialize\s*\("#,
+    )
+    .unwrap()
+});
+
+/// A struct field that may map to an env var
+struct StructField {
+    name: String,
+    /// `#[serde(rename = "...")]`, or the struct's `rename_all` applied
+    rename: Option<String>,
+    /// Env var bound through a clap `env` attribute
+    clap_env: Option<String>,
+    default_value: Option<String>,
+    optional: bool,
+    line: usize,
+    column: usize,
+    context: String,
+}
+
+impl StructField {
+    /// The name a serde-based loader (envy, config) reads for this field
+    fn serde_env_name(&self, prefix: &str) -> String {
+        let base = self.rename.as_deref().unwrap_or(&self.name);
+        format!("{}{}", prefix, base.to_uppercase())
+    }
+}
 
 impl RustScanner {
     pub fn new() -> Self {
         Self
     }
+
+    /// Parse struct definitions and the env-relevant attributes of their fields
+    fn parse_structs(content: &str) -> HashMap<String, Vec<StructField>> {
+        let mut structs: HashMap<String, Vec<StructField>> = HashMap::new();
+        let mut current: Option<String> = None;
+        let mut depth = 0i32;
+        let mut attrs = String::new();
+        // Open `[` of an attribute spanning several lines
+        let mut attr_depth = 0i32;
+        // `#[serde(rename_all = "...")]` on the current struct
+        let mut rename_all: Option<String> = None;
+
+        for (line_num, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+
+            if current.is_none() {
+                // Struct attributes, which may span several lines
+                if attr_depth > 0 || trimmed.starts_with("#[") {
+                    attrs.push_str(trimmed);
+                    attrs.push(' ');
+                    attr_depth += bracket_delta(trimmed);
+                    continue;
+                }
+                if let Some(cap) = STRUCT_DEF.captures(line) {
+                    if line.contains('{') {
+                        current = Some(cap[1].to_string());
+                        depth = brace_delta(line);
+                        rename_all = SERDE_RENAME_ALL.captures(&attrs).map(|c| c[1].to_string());
+                        structs.entry(cap[1].to_string()).or_default();
+                    }
+                }
+                // Doc comments and blank lines may sit between attributes
+                if !trimmed.is_empty() && !trimmed.starts_with("//") {
+                    attrs.clear();
+                }
+                continue;
+            }
+
+            // Collect field attributes, which may span several lines
+            if attr_depth > 0 || trimmed.starts_with("#[") {
+                attrs.push_str(trimmed);
+                attrs.push(' ');
+                attr_depth += bracket_delta(trimmed);
+                continue;
+            }
+
+            depth += brace_delta(line);
+            if depth <= 0 {
+                current = None;
+                continue;
+            }
+
+            if let Some(cap) = STRUCT_FIELD.captures(line) {
+                let name = cap.get(1).unwrap();
+                let ty = cap[2].trim();
+
+                let clap = CLAP_ATTR.captures(&attrs).map(|c| c[1].to_string());
+                let clap_env = clap.as_deref().and_then(|inner| {
+                    if let Some(c) = CLAP_ENV_NAMED.captures(inner) {
+                        Some(c[1].to_string())
+                    } else if CLAP_ENV_BARE.is_match(inner) {
+                        Some(name.as_str().to_uppercase())
+                    } else {
+                        None
+                    }
+                });
+                let default_value = clap
+                    .as_deref()
+                    .and_then(|inner| CLAP_DEFAULT.captures(inner))
+                    .map(|c| c[1].trim().to_string());
+
+                let rename = SERDE_RENAME
+                    .captures(&attrs)
+                    .map(|c| c[1].to_string())
+                    .or_else(|| {
+                        rename_all
+                            .as_deref()
+                            .and_then(|rule| apply_rename_all(name.as_str(), rule))
+                    });
+
+                let field = StructField {
+                    name: name.as_str().to_string(),
+                    rename,
+                    clap_env,
+                    optional: ty.starts_with("Option<")
+                        || SERDE_DEFAULT.is_match(&attrs)
+                        || default_value.is_some(),
+                    default_value,
+                    line: line_num + 1,
+                    column: name.start() + 1,
+                    context: trimmed.to_string(),
+                };
+
+                if let Some(fields) = current.as_ref().and_then(|s| structs.get_mut(s)) {
+                    fields.push(field);
+                }
+            }
+            attrs.clear();
+        }
+
+        structs
+    }
+
+    /// Find `(struct name, prefix)` pairs deserialized from the environment
+    fn find_env_bindings(content: &str) -> Vec<(String, String)> {
+        let mut bindings = Vec::new();
+
+        for cap in ENVY_TURBOFISH.captures_iter(content) {
+            let prefix = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            bindings.push((cap[2].to_string(), prefix.to_string()));
+        }
+
+        for cap in ENVY_ANNOTATED.captures_iter(content) {
+            let prefix = cap.get(2).map(|m| m.as_str()).unwrap_or("");
+            bindings.push((cap[1].to_string(), prefix.to_string()));
+        }
+
+        // The config crate joins prefix and key with "_"
+        if let Some(cap) = CONFIG_ENV_PREFIX.captures(content) {
+            let prefix = format!("{}_", &cap[1]);
+            for cap in CONFIG_DESERIALIZE.captures_iter(content) {
+                if let Some(ty) = cap.get(1).or_else(|| cap.get(2)) {
+                    bindings.push((ty.as_str().to_string(), prefix.clone()));
+                }
+            }
+        }
+
+        bindings
+    }
+}
+
+/// A snake_case field name as serde's `rename_all` rule spells it
+fn apply_rename_all(field: &str, rule: &str) -> Option<String> {
+    let words: Vec<&str> = field.split('_').filter(|w| !w.is_empty()).collect();
+    let capitalize = |w: &&str| {
+        let mut chars = w.chars();
+        chars.next().map_or(String::new(), |c| {
+            c.to_uppercase().chain(chars).collect::<String>()
+        })
+    };
+    let renamed = match rule {
+        "lowercase" => field.to_lowercase(),
+        "UPPERCASE" => field.to_uppercase(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "camelCase" => {
+            let mut renamed = words.first().map_or(String::new(), |w| w.to_string());
+            renamed.extend(words.iter().skip(1).map(capitalize));
+            renamed
+        }
+        _ => return None,
+    };
+    Some(renamed)
+}
+
+fn brace_delta(line: &str) -> i32 {
+    line.chars().fold(0, |acc, c| match c {
+        '{' => acc + 1,
+        '}' => acc - 1,
+        _ => acc,
+    })
+}
+
+fn bracket_delta(line: &str) -> i32 {
+    line.chars().fold(0, |acc, c| match c {
+        '[' => acc + 1,
+        ']' => acc - 1,
+        _ => acc,
+    })
 }
 
 impl Default for RustScanner {
@@ -45,13 +300,20 @@ impl LanguageScanner for RustScanner {
         let mut usages = Vec::new();
         let mut seen = std::collections::HashSet::new();
 
-        let patterns: Vec<&Lazy<Regex>> =
-            vec![&ENV_VAR, &ENV_VAR_OS, &ENV_MACRO, &OPTION_ENV_MACRO];
+        // option_env! yields None instead of failing the build. It must be
+        // matched before env!, which also matches inside it.
+        let patterns: Vec<(&Lazy<Regex>, Option<bool>)> = vec![
+            (&ENV_VAR, None),
+            (&ENV_VAR_OS, None),
+            (&OPTION_ENV_MACRO, Some(false)),
+            (&ENV_MACRO, Some(true)),
+            (&DOTENVY_VAR, None),
+        ];
 
         for (line_num, line) in content.lines().enumerate() {
             let line_num = line_num + 1;
 
-            for pattern in &patterns {
+            for (pattern, required) in &patterns {
                 for cap in pattern.captures_iter(line) {
                     if let Some(m) = cap.get(1) {
                         let key = (line_num, m.start(), m.as_str().to_string());
@@ -63,6 +325,9 @@ impl LanguageScanner for RustScanner {
                                 column: m.start() + 1,
                                 language: Language::Rust,
                                 context: Some(line.trim().to_string()),
+                                default_value: None,
+                                required: *required,
+                                exposure: None,
                             });
                         }
                     }
@@ -70,6 +335,50 @@ impl LanguageScanner for RustScanner {
             }
         }
 
+        let structs = Self::parse_structs(content);
+
+        // clap `env` attributes
+        let mut fields: Vec<&StructField> = structs.values().flatten().collect();
+        fields.sort_by_key(|f| f.line);
+        for field in fields {
+            if let Some(name) = &field.clap_env {
+                usages.push(EnvVarUsage {
+                    name: name.clone(),
+                    file_path: file_path.to_path_buf(),
+                    line: field.line,
+                    column: field.column,
+                    language: Language::Rust,
+                    context: Some(field.context.clone()),
+                    default_value: field.default_value.clone(),
+                    required: Some(!field.optional),
+                    exposure: None,
+                });
+            }
+        }
+
+        // Structs deserialized by envy or config, one var per field
+        for (struct_name, prefix) in Self::find_env_bindings(content) {
+            for field in structs.get(&struct_name).into_iter().flatten() {
+                usages.push(EnvVarUsage {
+                    name: field.serde_env_name(&prefix),
+                    file_path: file_path.to_path_buf(),
+                    line: field.line,
+                    column: field.column,
+                    language: Language::Rust,
+                    context: Some(field.context.clone()),
+                    default_value: None,
+                    required: Some(!field.optional),
+                    exposure: None,
+                });
+            }
+        }
+
+        usages.extend(expansion::scan_expansions(
+            Language::Rust,
+            content,
+            file_path,
+        ));
+
         usages
     }
 }
@@ -112,5 +421,126 @@ mod tests {
         let usages = scanner.scan(content, Path::new("main.rs"));
         assert_eq!(usages.len(), 1);
         assert_eq!(usages[0].name, "DEBUG");
+        assert_eq!(usages[0].required, Some(false));
+    }
+
+    #[test]
+    fn test_dotenvy_var() {
+        let scanner = RustScanner::new();
+        let content = r#"let url = dotenvy::var("REDIS_URL")?;"#;
+        let usages = scanner.scan(content, Path::new("main.rs"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "REDIS_URL");
+    }
+
+    #[test]
+    fn test_envy_struct_fields() {
+        let scanner = RustScanner::new();
+        let content = r#"
+#[derive(Deserialize)]
+struct Config {
+    database_url: String,
+    #[serde(rename = "http_port")]
+    port: u16,
+    log_level: Option<String>,
+}
+
+let config = envy::prefixed("APP_").from_env::<Config>()?;
+"#;
+        let usages = scanner.scan(content, Path::new("config.rs"));
+        let names: Vec<_> = usages.iter().map(|u| u.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["APP_DATABASE_URL", "APP_HTTP_PORT", "APP_LOG_LEVEL"]
+        );
+        assert_eq!(usages[0].required, Some(true));
+        assert_eq!(usages[2].required, Some(false));
+    }
+
+    #[test]
+    fn test_struct_fields_with_commas_and_rename_all() {
+        let scanner = RustScanner::new();
+        let content = r#"
+#[cfg(feature = "env")] mod env {}
+
+#[derive(Deserialize)]
+#[serde(
+    rename_all = "camelCase"
+)]
+/// Loaded from the environment
+struct Config {
+    headers: HashMap<String, String>,
+    port_range: Option<(u16, u16)>, // inclusive
+    #[serde(rename = "LOG")]
+    log_level: String,
+}
+
+let config = envy::from_env::<Config>()?;
+"#;
+        let usages = scanner.scan(content, Path::new("config.rs"));
+        let found: Vec<_> = usages
+            .iter()
+            .map(|u| (u.name.as_str(), u.is_optional()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![("HEADERS", false), ("PORTRANGE", true), ("LOG", false)]
+        );
+    }
+
+    #[test]
+    fn test_apply_rename_all() {
+        assert_eq!(
+            apply_rename_all("api_base_url", "camelCase").as_deref(),
+            Some("apiBaseUrl")
+        );
+        assert_eq!(
+            apply_rename_all("api_base_url", "PascalCase").as_deref(),
+            Some("ApiBaseUrl")
+        );
+        assert_eq!(
+            apply_rename_all("api_base_url", "SCREAMING-KEBAB-CASE").as_deref(),
+            Some("API-BASE-URL")
+        );
+        assert_eq!(apply_rename_all("api_base_url", "Title Case"), None);
+    }
+
+    #[test]
+    fn test_clap_env_attribute() {
+        let scanner = RustScanner::new();
+        let content = r#"
+#[derive(Parser)]
+struct Args {
+    #[arg(long, env = "LISTEN_ADDR", default_value = "0.0.0.0:8080")]
+    listen: String,
+    #[arg(long, env)]
+    api_token: Option<String>,
+}
+"#;
+        let usages = scanner.scan(content, Path::new("cli.rs"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "LISTEN_ADDR");
+        assert_eq!(usages[0].default_value.as_deref(), Some("0.0.0.0:8080"));
+        assert_eq!(usages[1].name, "API_TOKEN");
+        assert!(usages[1].is_optional());
+    }
+
+    #[test]
+    fn test_config_environment_prefix() {
+        let scanner = RustScanner::new();
+        let content = r#"
+#[derive(Deserialize)]
+pub struct Settings {
+    pub debug: bool,
+}
+
+let settings = Config::builder()
+    .add_source(config::Environment::with_prefix("APP"))
+    .build()?
+    .try_deserialize::<Settings>()?;
+"#;
+        let usages = scanner.scan(content, Path::new("settings.rs"));
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "APP_DEBUG");
     }
 }
@@ -0,0 +1,232 @@
+use regex::Regex;
+use std::path::Path;
+
+use super::LanguageScanner;
+use crate::config::{ScanConfig, TemplatePlaceholder};
+use crate::types::{EnvVarUsage, Language};
+
+/// Scanner for env placeholders substituted into templates, such as Vite's
+/// `%VITE_APP_TITLE%` in `index.html` or Ansible's `lookup('env', 'HOME')`
+pub struct TemplateScanner {
+    language: Language,
+    /// Regex with a `name` group and an optional `default` group
+    regex: Regex,
+    extensions: Vec<String>,
+    /// Names the tool provides a value for when they are not set
+    optional: Vec<&'static str>,
+}
+
+impl TemplateScanner {
+    /// Build a built-in preset by name
+    pub fn preset(name: &str) -> Option<Self> {
+        let (language, pattern, extensions, optional): (_, _, &[&str], _) = match name
+            .to_lowercase()
+            .as_str()
+        {
+            // https://vitejs.dev/guide/env-and-mode#html-env-replacement
+            "vite" => (
+                Language::JavaScript,
+                r"%(?P<name>VITE_[A-Za-z0-9_]*)%",
+                &["html"],
+                vec![],
+            ),
+            // CRA fills PUBLIC_URL from `homepage` when it is not set
+            "cra" => (
+                Language::JavaScript,
+                r"%(?P<name>REACT_APP_[A-Za-z0-9_]*|PUBLIC_URL)%",
+                &["html"],
+                vec!["PUBLIC_URL"],
+            ),
+            // dbt-style env_var('X', 'default') and environ['X']
+            "jinja" => (
+                Language::Custom("jinja"),
+                r#"(?:\benv_var\(\s*|\benviron(?:\.get\(\s*|\[\s*))['"](?P<name>[A-Za-z_][A-Za-z0-9_]*)['"](?:\s*,\s*['"](?P<default>[^'"]*)['"])?"#,
+                &["j2", "jinja", "jinja2", "html", "sql"],
+                vec![],
+            ),
+            // lookup('env', 'X') with an optional default= argument
+            "ansible" => (
+                Language::Custom("ansible"),
+                r#"\b(?:lookup|query|q)\(\s*['"](?:ansible\.builtin\.)?env['"]\s*,\s*['"](?P<name>[A-Za-z_][A-Za-z0-9_]*)['"](?:\s*,\s*default\s*=\s*['"](?P<default>[^'"]*)['"])?"#,
+                &["yml", "yaml", "j2"],
+                vec![],
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            language,
+            regex: Regex::new(pattern).unwrap(),
+            extensions: extensions.iter().map(|e| e.to_string()).collect(),
+            optional,
+        })
+    }
+
+    /// Build a scanner from a `[[scan.templates]]` entry
+    pub fn from_placeholder(placeholder: &TemplatePlaceholder) -> Self {
+        let name = if placeholder.prefix.is_empty() {
+            r"[A-Za-z_][A-Za-z0-9_]*".to_string()
+        } else {
+            format!(r"{}[A-Za-z0-9_]*", regex::escape(&placeholder.prefix))
+        };
+        let pattern = format!(
+            r"{}\s*(?P<name>{})\s*{}",
+            regex::escape(&placeholder.open),
+            name,
+            regex::escape(&placeholder.close)
+        );
+
+        Self {
+            language: Language::Custom("template"),
+            regex: Regex::new(&pattern).unwrap(),
+            extensions: placeholder
+                .extensions
+                .iter()
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .collect(),
+            optional: Vec::new(),
+        }
+    }
+
+    /// Every template scanner enabled by the scan config
+    pub fn from_scan_config(config: &ScanConfig) -> Vec<Self> {
+        config
+            .template_presets
+            .iter()
+            .filter_map(|p| Self::preset(p))
+            .chain(config.templates.iter().map(Self::from_placeholder))
+            .collect()
+    }
+
+    /// File extensions this scanner reads
+    pub fn file_extensions(&self) -> &[String] {
+        &self.extensions
+    }
+}
+
+impl LanguageScanner for TemplateScanner {
+    fn language(&self) -> Language {
+        self.language
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        // Extensions depend on the preset or config, see matches_file
+        &[]
+    }
+
+    fn matches_file(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| self.extensions.contains(&e.to_lowercase()))
+            .unwrap_or(false)
+    }
+
+    fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        let mut usages = Vec::new();
+
+        for (line_num, line) in content.lines().enumerate() {
+            let line_num = line_num + 1;
+
+            for cap in self.regex.captures_iter(line) {
+                let name = cap.name("name").unwrap();
+                let default_value = cap.name("default").map(|d| d.as_str().to_string());
+                let required = if default_value.is_some() || self.optional.contains(&name.as_str())
+                {
+                    Some(false)
+                } else {
+                    None
+                };
+
+                usages.push(EnvVarUsage {
+                    name: name.as_str().to_string(),
+                    file_path: file_path.to_path_buf(),
+                    line: line_num,
+                    column: name.start() + 1,
+                    language: self.language,
+                    context: Some(line.trim().to_string()),
+                    default_value,
+                    required,
+                    exposure: None,
+                });
+            }
+        }
+
+        usages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vite_and_cra_html() {
+        let vite = TemplateScanner::preset("vite").unwrap();
+        let cra = TemplateScanner::preset("cra").unwrap();
+        let content = r#"<title>%VITE_APP_TITLE%</title>
+<link rel="icon" href="%PUBLIC_URL%/favicon.ico" />
+<meta name="version" content="%REACT_APP_VERSION%" />"#;
+        let path = Path::new("index.html");
+
+        let usages = vite.scan(content, path);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "VITE_APP_TITLE");
+        assert_eq!(usages[0].column, 9);
+
+        let usages = cra.scan(content, path);
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "PUBLIC_URL");
+        assert!(usages[0].is_optional());
+        assert_eq!(usages[1].name, "REACT_APP_VERSION");
+        assert!(!usages[1].is_optional());
+    }
+
+    #[test]
+    fn test_ansible_lookup() {
+        let scanner = TemplateScanner::preset("ansible").unwrap();
+        let content = r#"home: "{{ lookup('env', 'HOME') }}"
+token: "{{ lookup('ansible.builtin.env', 'API_TOKEN', default='none') }}""#;
+        let usages = scanner.scan(content, Path::new("playbook.yml"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "HOME");
+        assert_eq!(usages[0].language.id(), "ansible");
+        assert_eq!(usages[1].name, "API_TOKEN");
+        assert_eq!(usages[1].default_value.as_deref(), Some("none"));
+    }
+
+    #[test]
+    fn test_jinja_env_var() {
+        let scanner = TemplateScanner::preset("jinja").unwrap();
+        let content = r#"host: "{{ env_var('DBT_HOST', 'localhost') }}"
+password: "{{ environ['DB_PASSWORD'] }}""#;
+        let usages = scanner.scan(content, Path::new("profiles.j2"));
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "DBT_HOST");
+        assert_eq!(usages[0].default_value.as_deref(), Some("localhost"));
+        assert_eq!(usages[1].name, "DB_PASSWORD");
+    }
+
+    #[test]
+    fn test_custom_delimiters() {
+        let placeholder = TemplatePlaceholder {
+            open: "${".to_string(),
+            close: "}".to_string(),
+            extensions: vec![".tmpl".to_string()],
+            prefix: "APP_".to_string(),
+        };
+        let scanner = TemplateScanner::from_placeholder(&placeholder);
+        assert!(scanner.matches_file(Path::new("nginx.conf.tmpl")));
+
+        let usages = scanner.scan(
+            "listen ${APP_PORT}; root ${HOME};",
+            Path::new("nginx.conf.tmpl"),
+        );
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "APP_PORT");
+    }
+
+    #[test]
+    fn test_unknown_preset() {
+        assert!(TemplateScanner::preset("handlebars").is_none());
+    }
+}
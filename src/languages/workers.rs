@@ -0,0 +1,158 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use super::LanguageScanner;
+use crate::types::{EnvVarUsage, Language};
+
+/// Scanner for Cloudflare Worker entrypoints, where env arrives as the `env`
+/// argument of `fetch(request, env)` rather than through `process.env`
+pub struct WorkersScanner;
+
+// A module exporting Worker handlers or entrypoint classes
+static ENTRYPOINT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\bexport\s+default\s*\{|\bExportedHandler\b|\bextends\s+(?:WorkerEntrypoint|DurableObject|WorkflowEntrypoint)\b",
+    )
+    .unwrap()
+});
+
+// Second parameter of a handler definition: `fetch(request, env, ctx) {` or
+// `fetch: async (request, env) =>`, but not a `fetch(url, init)` call
+static HANDLER_ENV_PARAM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:fetch|scheduled|queue|email|tail|trace)\s*(?::\s*(?:async\s*)?)?\(\s*[A-Za-z_$][\w$]*\s*(?::\s*[^,()]+)?,\s*([A-Za-z_$][\w$]*)[^()]*\)\s*(?::\s*[^{;]+)?(?:\{|=>)",
+    )
+    .unwrap()
+});
+
+// A full member chain such as `env.MY_KV.get` or `this.env.LIMIT`; the
+// leading group keeps chains like `process.env` whole
+static MEMBER_CHAIN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:^|[^\w$.])([A-Za-z_$][\w$]*(?:\??\.[A-Za-z_$][\w$]*)+)").unwrap());
+
+impl WorkersScanner {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Names the env object goes by in this file
+    fn env_names(content: &str) -> Vec<String> {
+        let mut names = vec!["env".to_string()];
+        for cap in HANDLER_ENV_PARAM.captures_iter(content) {
+            let name = cap[1].to_string();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+impl Default for WorkersScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LanguageScanner for WorkersScanner {
+    fn language(&self) -> Language {
+        Language::JavaScript
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["js", "mjs", "cjs", "jsx", "ts", "mts", "cts", "tsx"]
+    }
+
+    fn scan(&self, content: &str, file_path: &Path) -> Vec<EnvVarUsage> {
+        if !ENTRYPOINT.is_match(content) {
+            return Vec::new();
+        }
+        let env_names = Self::env_names(content);
+
+        let mut usages = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            for cap in MEMBER_CHAIN.captures_iter(line) {
+                let chain = cap.get(1).unwrap();
+                let segments: Vec<&str> = chain.as_str().split('.').collect();
+                let object = |i: usize| segments.get(i).map(|s| s.trim_end_matches('?'));
+
+                // env.X, or this.env.X / c.env.X for the `env` name itself
+                let index = match object(0) {
+                    Some("this" | "c" | "ctx" | "context") if object(1) == Some("env") => 2,
+                    Some(name) if env_names.iter().any(|n| n == name) => 1,
+                    _ => continue,
+                };
+                let Some(name) = object(index) else {
+                    continue;
+                };
+                if !name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+                    continue;
+                }
+
+                let offset: usize = segments[..index].iter().map(|s| s.len() + 1).sum();
+                usages.push(EnvVarUsage {
+                    name: name.to_string(),
+                    file_path: file_path.to_path_buf(),
+                    line: line_num + 1,
+                    column: chain.start() + offset + 1,
+                    language: Language::JavaScript,
+                    context: Some(line.trim().to_string()),
+                    default_value: None,
+                    required: None,
+                    exposure: None,
+                });
+            }
+        }
+
+        usages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(content: &str) -> Vec<String> {
+        WorkersScanner::new()
+            .scan(content, Path::new("src/index.ts"))
+            .into_iter()
+            .map(|u| u.name)
+            .collect()
+    }
+
+    #[test]
+    fn test_fetch_handler() {
+        let content = r#"export default {
+  async fetch(request: Request, env: Env, ctx: ExecutionContext) {
+    const token = env.API_TOKEN;
+    const value = await env.MY_KV.get("key");
+    const url = process.env.NOT_A_BINDING;
+    return new Response(token);
+  },
+};"#;
+        assert_eq!(names(content), vec!["API_TOKEN", "MY_KV"]);
+    }
+
+    #[test]
+    fn test_renamed_env_and_durable_object() {
+        let content = r#"export class Counter extends DurableObject {
+  async increment() {
+    return this.env.COUNTER_LIMIT;
+  }
+}
+export default {
+  fetch(req, bindings) {
+    const init = { method: "GET" };
+    return fetch(bindings.ORIGIN_URL, init).then((r) => r.headers.get(init.method));
+  },
+};"#;
+        assert_eq!(names(content), vec!["COUNTER_LIMIT", "ORIGIN_URL"]);
+    }
+
+    #[test]
+    fn test_non_entrypoint_ignored() {
+        let content = "export function config(env) { return env.API_TOKEN; }";
+        assert!(names(content).is_empty());
+    }
+}
@@ -0,0 +1,877 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use super::constants::{file_constants, key_constants, resolve_key};
+use super::imports::{file_imports, module_id, Import};
+use super::ScanContext;
+use crate::config::WrapperConfig;
+use crate::types::{EnvVarUsage, Language};
+
+/// A project function whose argument names the env var it reads, such as
+/// `function requireEnv(name) { return process.env[name] }`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvWrapper {
+    pub name: String,
+    /// Language whose files call the wrapper; None for every language
+    pub language: Option<Language>,
+    /// Index of the argument holding the var name
+    pub arg: usize,
+    /// Index of the argument holding a fallback value
+    pub default_arg: Option<usize>,
+    /// Whether the wrapper fails when the var is unset
+    pub required: Option<bool>,
+    /// File defining a detected wrapper; None for declared wrappers, which
+    /// apply project-wide
+    pub file: Option<PathBuf>,
+    /// Whether calls may go through a receiver, as for methods (`settings.env(..)`)
+    pub method: bool,
+    /// Class, module or type a detected method is defined on
+    pub class: Option<String>,
+}
+
+impl EnvWrapper {
+    /// Wrappers declared in `[[scan.wrappers]]`, one per listed language
+    pub fn from_config(config: &WrapperConfig) -> Vec<Self> {
+        let wrapper = |language| Self {
+            name: config.name.clone(),
+            language,
+            arg: config.arg,
+            default_arg: config.default_arg,
+            required: config.required,
+            file: None,
+            method: true,
+            class: None,
+        };
+
+        if config.languages.is_empty() {
+            return vec![wrapper(None)];
+        }
+        config
+            .languages
+            .iter()
+            .filter_map(|l| Language::from_name(l))
+            .map(|l| wrapper(Some(l)))
+            .collect()
+    }
+}
+
+/// How a function body is delimited
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Body {
+    Braces,
+    Indent,
+}
+
+/// How a parameter name is written
+#[derive(Clone, Copy)]
+enum Param {
+    /// `name: Type = default` (JS/TS, Python, Ruby, Rust)
+    NameFirst,
+    /// `name Type` (Go)
+    FirstToken,
+    /// `Type name` (Java, C#)
+    LastToken,
+    /// `?Type $name = default` (PHP)
+    Dollar,
+}
+
+struct Syntax {
+    /// Function definitions with `name` and `params` groups
+    headers: Vec<&'static Regex>,
+    body: Body,
+    param: Param,
+    /// Env reads keyed by a (possibly qualified) identifier, captured in group 1
+    access: &'static Regex,
+}
+
+static JS_FUNCTION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\bfunction\s*\*?\s*(?P<name>[A-Za-z_$][\w$]*)\s*(?:<[^>(]*>)?\s*\((?P<params>[^)]*)\)",
+    )
+    .unwrap()
+});
+static JS_ARROW: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:const|let|var)\s+(?P<name>[A-Za-z_$][\w$]*)\s*(?::[^=]+)?=\s*(?:async\s+)?(?:\((?P<params>[^)]*)\)|(?P<param>[A-Za-z_$][\w$]*))\s*(?::[^=]+)?=>",
+    )
+    .unwrap()
+});
+static JS_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:(?:process\??\.env|Bun\??\.env|import\.meta\.env)(?:\?\.)?\[\s*|Deno\??\.env\??\.get\(\s*)([A-Za-z_$][\w$]*(?:\.[A-Za-z_$][\w$]*)*)\s*[\])]",
+    )
+    .unwrap()
+});
+
+static PY_DEF: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*def\s+(?P<name>\w+)\s*\((?P<params>[^)]*)\)").unwrap());
+static PY_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:environ\[\s*|environ\.get\(\s*|getenv\(\s*)([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*[\]),]",
+    )
+    .unwrap()
+});
+
+static GO_FUNC: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfunc\s+(?:\([^)]*\)\s*)?(?P<name>\w+)\s*\((?P<params>[^)]*)\)").unwrap()
+});
+static GO_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"os\.(?:Getenv|LookupEnv)\(\s*([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\)").unwrap()
+});
+
+static RUBY_DEF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^[ \t]*def\s+(?:self\.)?(?P<name>\w+[?!]?)[ \t]*\(?(?P<params>[^)\n]*)\)?")
+        .unwrap()
+});
+static RUBY_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"ENV(?:\[\s*|\.fetch\(?\s*)([A-Za-z_]\w*(?:(?:\.|::)[A-Za-z_]\w*)*)\s*[\]),\s]")
+        .unwrap()
+});
+
+static PHP_FUNCTION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\bfunction\s+(?P<name>\w+)\s*\((?P<params>[^)]*)\)").unwrap());
+static PHP_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:\bgetenv\(\s*|\$_ENV\[\s*|\$_SERVER\[\s*|\benv\(\s*)(\$?[A-Za-z_]\w*(?:::[A-Za-z_]\w*)?)\s*[\]),]",
+    )
+    .unwrap()
+});
+
+static RUST_FN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bfn\s+(?P<name>\w+)\s*(?:<[^>]*>)?\s*\((?P<params>[^)]*)\)").unwrap()
+});
+static RUST_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?:env::var(?:_os)?|dotenvy::var)\(\s*&?([A-Za-z_]\w*(?:::[A-Za-z_]\w*)*)\s*\)")
+        .unwrap()
+});
+
+// Method declarations: modifiers, a return type, then name(params)
+static JVM_METHOD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\b(?:public|private|protected|internal|static)\s[\w\s<>\[\],?]*?\b(?P<name>\w+)\s*\((?P<params>[^)]*)\)",
+    )
+    .unwrap()
+});
+static JAVA_ACCESS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"System\.getenv\(\s*([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*\)").unwrap());
+static CSHARP_ACCESS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"GetEnvironmentVariable\(\s*([A-Za-z_]\w*(?:\.[A-Za-z_]\w*)*)\s*[,)]").unwrap()
+});
+
+// Bodies that fail when the var is unset
+static FAILS_WHEN_UNSET: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"\bthrow\b|\braise\b|\bpanic!|\.expect\(|\.unwrap\(\)|\blog\.Fatal|\bexit\(|\bdie\(",
+    )
+    .unwrap()
+});
+
+/// A name used as a fallback: `|| alt`, `?? alt`, `or alt`, `?: alt`, or the
+/// second argument of a lookup such as `environ.get(key, fallback)`
+static FALLBACK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?:\|\||\?\?|\bor\b|\?:)\s*\$?(?P<alt>\w+)\b|\(\s*&?\$?(?P<key>[\w$]+)\s*,\s*\$?(?P<fallback>\w+)\s*\)",
+    )
+    .unwrap()
+});
+
+/// Declarations a method may belong to, per language
+static JS_CLASS: Lazy<Regex> = Lazy::new(|| Regex::new(r"\bclass\s+(?P<name>\w+)").unwrap());
+static RUBY_CLASS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?m)^[ \t]*(?:class|module)\s+(?:\w+::)*(?P<name>\w+)").unwrap());
+static PHP_CLASS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:class|trait)\s+(?P<name>\w+)").unwrap());
+static JVM_CLASS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\b(?:class|record|struct|interface)\s+(?P<name>\w+)").unwrap());
+static RUST_IMPL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\bimpl(?:\s*<[^>]*>)?\s+(?:[\w:]+(?:<[^>]*>)?\s+for\s+)?(?:\w+::)*(?P<name>\w+)")
+        .unwrap()
+});
+static GO_RECEIVER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^func\s*\(\s*\w*\s*\*?(?P<name>\w+)").unwrap());
+
+/// Receivers naming the object or class a method is called from inside
+const SELF_RECEIVERS: &[&str] = &["self", "Self", "this", "$this", "cls", "static", "parent"];
+
+static QUOTED_NAME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^(?:"([A-Za-z_][A-Za-z0-9_]*)"|'([A-Za-z_][A-Za-z0-9_]*)'|`([A-Za-z_][A-Za-z0-9_]*)`)$"#,
+    )
+    .unwrap()
+});
+
+fn syntax(language: Language) -> Option<Syntax> {
+    Some(match language {
+        Language::JavaScript | Language::TypeScript => Syntax {
+            headers: vec![&*JS_FUNCTION, &*JS_ARROW],
+            body: Body::Braces,
+            param: Param::NameFirst,
+            access: &JS_ACCESS,
+        },
+        Language::Python => Syntax {
+            headers: vec![&*PY_DEF],
+            body: Body::Indent,
+            param: Param::NameFirst,
+            access: &PY_ACCESS,
+        },
+        Language::Go => Syntax {
+            headers: vec![&*GO_FUNC],
+            body: Body::Braces,
+            param: Param::FirstToken,
+            access: &GO_ACCESS,
+        },
+        Language::Ruby => Syntax {
+            headers: vec![&*RUBY_DEF],
+            body: Body::Indent,
+            param: Param::NameFirst,
+            access: &RUBY_ACCESS,
+        },
+        Language::Php => Syntax {
+            headers: vec![&*PHP_FUNCTION],
+            body: Body::Braces,
+            param: Param::Dollar,
+            access: &PHP_ACCESS,
+        },
+        Language::Rust => Syntax {
+            headers: vec![&*RUST_FN],
+            body: Body::Braces,
+            param: Param::NameFirst,
+            access: &RUST_ACCESS,
+        },
+        Language::Java => Syntax {
+            headers: vec![&*JVM_METHOD],
+            body: Body::Braces,
+            param: Param::LastToken,
+            access: &JAVA_ACCESS,
+        },
+        Language::CSharp => Syntax {
+            headers: vec![&*JVM_METHOD],
+            body: Body::Braces,
+            param: Param::LastToken,
+            access: &CSHARP_ACCESS,
+        },
+        Language::Custom(_) => return None,
+    })
+}
+
+/// Env reads whose key is an identifier rather than a literal, such as
+/// `process.env[KEY]` or `System.getenv(Keys.STRIPE)`
+pub(super) fn dynamic_access(language: Language) -> Option<&'static Regex> {
+    syntax(language).map(|s| s.access)
+}
+
+fn param_names(params: &str, style: Param) -> Vec<String> {
+    params
+        .split(',')
+        .map(|p| {
+            let p = p.trim();
+            let name = match style {
+                Param::NameFirst => p
+                    .trim_start_matches("...")
+                    .trim_start_matches('&')
+                    .trim_start_matches("mut ")
+                    .split([':', '=', '?'])
+                    .next()
+                    .unwrap_or_default(),
+                Param::FirstToken => p.split_whitespace().next().unwrap_or_default(),
+                Param::LastToken => p
+                    .split('=')
+                    .next()
+                    .unwrap_or_default()
+                    .split_whitespace()
+                    .last()
+                    .unwrap_or_default(),
+                Param::Dollar => p
+                    .split('$')
+                    .nth(1)
+                    .and_then(|n| n.split(|c: char| !c.is_alphanumeric() && c != '_').next())
+                    .unwrap_or_default(),
+            };
+            name.trim().to_string()
+        })
+        .filter(|n| !n.is_empty())
+        // Receivers are not passed in the argument list
+        .filter(|n| !matches!(n.as_str(), "self" | "cls" | "this"))
+        .collect()
+}
+
+/// Text of the function body starting at `start` (just past the header)
+fn body_at<'a>(content: &'a str, start: usize, header: &str, body: Body) -> &'a str {
+    let rest = &content[start..];
+    let line_end = rest.find('\n').unwrap_or(rest.len());
+
+    match body {
+        Body::Braces => {
+            // Expression-bodied arrow functions end with the line
+            if header.ends_with("=>") && !rest.trim_start().starts_with('{') {
+                return &rest[..line_end];
+            }
+            let open = match rest.find(['{', ';']) {
+                Some(i) if rest.as_bytes()[i] == b'{' => i,
+                _ => return &rest[..line_end],
+            };
+            let mut depth = 0;
+            for (i, c) in rest[open..].char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return &rest[open..open + i + 1];
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            &rest[open..]
+        }
+        Body::Indent => {
+            let line_start = content[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+            let indent = indentation(&content[line_start..]);
+            let mut end = (start + line_end + 1).min(content.len());
+            for line in content[end..].split_inclusive('\n') {
+                if !line.trim().is_empty() && indentation(line) <= indent {
+                    break;
+                }
+                end += line.len();
+            }
+            &content[start..end]
+        }
+    }
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Whether a function header declares a method rather than a free function
+fn is_method(language: Language, content: &str, header: regex::Match, params: &str) -> bool {
+    let first = params.split(',').next().unwrap_or_default().trim();
+    let first = first.trim_start_matches('&').trim_start_matches("mut ");
+    let receiver_param = ["self", "cls", "this"]
+        .iter()
+        .any(|r| first.split([':', ' ']).next() == Some(*r));
+
+    let line_start = content[..header.start()]
+        .rfind('\n')
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let declaration = &content[line_start..header.end()];
+    match language {
+        Language::Java | Language::CSharp => true,
+        Language::Go => declaration.contains("func ("),
+        Language::Ruby => declaration.contains("def self."),
+        Language::Php => ["public", "private", "protected", "static"]
+            .iter()
+            .any(|m| declaration.split_whitespace().any(|w| w == *m)),
+        _ => receiver_param,
+    }
+}
+
+/// Name of the class, module or type declared last in `before`, which a
+/// method whose header follows belongs to. Go methods name it in the header.
+fn class_name(language: Language, before: &str, header: &str) -> Option<String> {
+    let declaration = match language {
+        Language::Go => return GO_RECEIVER.captures(header).map(|c| c["name"].to_string()),
+        Language::JavaScript | Language::TypeScript | Language::Python => &*JS_CLASS,
+        Language::Ruby => &*RUBY_CLASS,
+        Language::Php => &*PHP_CLASS,
+        Language::Java | Language::CSharp => &*JVM_CLASS,
+        Language::Rust => &*RUST_IMPL,
+        Language::Custom(_) => return None,
+    };
+    declaration
+        .captures_iter(before)
+        .last()
+        .map(|c| c["name"].to_string())
+}
+
+/// Find functions in `content` that read the env var named by one of their
+/// parameters, and record them as wrappers for `language` defined in `file_path`
+pub fn collect_wrappers(
+    language: Language,
+    content: &str,
+    file_path: &Path,
+    context: &mut ScanContext,
+) {
+    let Some(syntax) = syntax(language) else {
+        return;
+    };
+
+    for header in &syntax.headers {
+        for cap in header.captures_iter(content) {
+            let whole = cap.get(0).unwrap();
+            let raw_params = cap
+                .name("params")
+                .or_else(|| cap.name("param"))
+                .map(|p| p.as_str())
+                .unwrap_or_default();
+            let params = param_names(raw_params, syntax.param);
+            if params.is_empty() {
+                continue;
+            }
+
+            let body = body_at(content, whole.end(), whole.as_str(), syntax.body);
+            let Some(arg) = syntax.access.captures_iter(body).find_map(|access| {
+                let key = access.get(1).unwrap().as_str();
+                params.iter().position(|p| p == key.trim_start_matches('$'))
+            }) else {
+                continue;
+            };
+
+            // A second parameter used as the fallback: `process.env[k] ?? fallback`
+            let fallbacks: Vec<&str> = FALLBACK
+                .captures_iter(body)
+                .filter_map(|c| {
+                    c.name("alt").or_else(|| {
+                        c.name("fallback")
+                            .filter(|_| c["key"].trim_start_matches('$') == params[arg].as_str())
+                    })
+                })
+                .map(|m| m.as_str())
+                .collect();
+            let default_arg = params
+                .iter()
+                .enumerate()
+                .position(|(i, p)| i != arg && fallbacks.contains(&p.as_str()));
+            let required = if default_arg.is_some() {
+                Some(false)
+            } else if FAILS_WHEN_UNSET.is_match(body) {
+                Some(true)
+            } else {
+                None
+            };
+
+            let method = is_method(language, content, whole, raw_params);
+            context.env_wrappers.push(EnvWrapper {
+                name: cap["name"].to_string(),
+                language: Some(language),
+                arg,
+                default_arg,
+                required,
+                file: Some(file_path.to_path_buf()),
+                method,
+                class: method
+                    .then(|| class_name(language, &content[..whole.start()], whole.as_str()))
+                    .flatten(),
+            });
+        }
+    }
+}
+
+/// Arguments of the call whose argument list starts at `start`, as
+/// (offset, text) pairs. Stops at the closing paren, or at the end of the
+/// line for paren-less Ruby calls.
+pub(super) fn call_arguments(content: &str, start: usize, parens: bool) -> Vec<(usize, &str)> {
+    let mut args = Vec::new();
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+    let mut arg_start = start;
+
+    for (i, c) in content[start..].char_indices() {
+        let at = start + i;
+        if let Some(q) = quote {
+            if c == q {
+                quote = None;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' | '`' => quote = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth > 0 => depth -= 1,
+            ')' if parens => {
+                args.push((arg_start, &content[arg_start..at]));
+                return args;
+            }
+            '\n' | ';' | ')' if !parens => {
+                args.push((arg_start, &content[arg_start..at]));
+                return args;
+            }
+            ',' if depth == 0 => {
+                args.push((arg_start, &content[arg_start..at]));
+                arg_start = at + 1;
+            }
+            _ => {}
+        }
+    }
+    args
+}
+
+/// Regex matching a call to any of the wrappers, with `name` and, for
+/// parenthesized calls, `paren` groups
+pub(super) fn wrapper_call_pattern(wrappers: &[EnvWrapper]) -> Option<Regex> {
+    let mut names: Vec<&str> = wrappers.iter().map(|w| w.name.as_str()).collect();
+    if names.is_empty() {
+        return None;
+    }
+    names.sort_unstable();
+    names.dedup();
+    let pattern = format!(
+        r"(?:^|[^\w$])(?P<name>{})(?:\s*(?P<paren>\()|[ \t]+[\x22'])",
+        names
+            .iter()
+            .map(|n| regex::escape(n))
+            .collect::<Vec<_>>()
+            .join("|")
+    );
+    Regex::new(&pattern).ok()
+}
+
+/// The receiver a call goes through, such as `settings` in `settings.env(`,
+/// or None for a bare call
+fn call_receiver(before: &str) -> Option<&str> {
+    let before = before.trim_end();
+    let rest = ["?.", ".", "::", "->"]
+        .iter()
+        .find_map(|op| before.strip_suffix(op))?;
+    let rest = rest.trim_end();
+    let start = rest
+        .rfind(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    Some(&rest[start..])
+}
+
+/// Whether some line of `content` binds `receiver` to an instance of
+/// `class`: `settings = Settings()`, `cfg := &Config{}`, `$env = new Env`,
+/// `let c: Config`, or `private final Config config`
+fn binds_instance(content: &str, receiver: &str, class: &str) -> bool {
+    content.lines().any(|line| {
+        let words: Vec<&str> = line
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .filter(|w| !w.is_empty())
+            .collect();
+        let Some(at) = words.iter().position(|w| *w == receiver) else {
+            return false;
+        };
+        words[at + 1..].contains(&class) || (at > 0 && words[at - 1] == class)
+    })
+}
+
+/// Whether a wrapper can be called from `file_path`. Declared wrappers apply
+/// everywhere. Detected ones are visible in their own file, files of the same
+/// package (Go, Java, C#) and files importing their module. Calls through a
+/// receiver must go through a binding of that import, or reach a method
+/// through `self`/`this`, its class, or an instance of it.
+fn in_scope(
+    wrapper: &EnvWrapper,
+    language: Language,
+    file_path: &Path,
+    content: &str,
+    receiver: Option<&str>,
+    imports: &[Import],
+) -> bool {
+    if !wrapper.language.is_none_or(|l| l == language) {
+        return false;
+    }
+    let Some(file) = &wrapper.file else {
+        return receiver.is_none() || wrapper.method;
+    };
+
+    let module = module_id(file, language);
+    let importing: Vec<&Import> = imports.iter().filter(|i| i.names(&module)).collect();
+    let visible = file == file_path
+        || (matches!(language, Language::Go | Language::Java | Language::CSharp)
+            && file.parent() == file_path.parent())
+        || !importing.is_empty();
+
+    let Some(receiver) = receiver else {
+        return visible;
+    };
+    if importing
+        .iter()
+        .any(|i| i.bindings.iter().any(|b| b == receiver))
+    {
+        return true;
+    }
+    if !wrapper.method {
+        return false;
+    }
+    let class = wrapper.class.as_deref();
+    class == Some(receiver)
+        || (visible
+            && (SELF_RECEIVERS.contains(&receiver)
+                || class.is_some_and(|c| binds_instance(content, receiver, c))))
+}
+
+/// Report literal var names passed to known wrappers
+pub fn scan_wrapper_calls(
+    language: Language,
+    content: &str,
+    file_path: &Path,
+    context: &ScanContext,
+) -> Vec<EnvVarUsage> {
+    let Some(calls) = context.wrapper_calls() else {
+        return Vec::new();
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let constants = file_constants(language, content);
+    let imports = file_imports(language, content, file_path);
+    let keys = key_constants(language, file_path, &imports, context);
+    let mut usages = Vec::new();
+
+    for cap in calls.captures_iter(content) {
+        let name_match = cap.name("name").unwrap();
+        let receiver = call_receiver(&content[..name_match.start()]);
+        // Declared wrappers win over detected ones, then one from this file
+        let mut candidates: Vec<&EnvWrapper> = context
+            .env_wrappers
+            .iter()
+            .filter(|w| w.name == name_match.as_str())
+            .filter(|w| in_scope(w, language, file_path, content, receiver, &imports))
+            .collect();
+        candidates.sort_by_key(|w| (w.file.is_some(), w.file.as_deref() != Some(file_path)));
+        let Some(wrapper) = candidates.first().copied() else {
+            continue;
+        };
+        let (args_start, parens) = match cap.name("paren") {
+            Some(p) => (p.end(), true),
+            // Ruby-style `require_env "X"`
+            None => (cap.get(0).unwrap().end() - 1, false),
+        };
+        let args = call_arguments(content, args_start, parens);
+
+        let Some(&(offset, text)) = args.get(wrapper.arg) else {
+            continue;
+        };
+        // A literal name, or a constant holding one
+        let trimmed = text.trim();
+        let lead = text.len() - text.trim_start().len();
+        let (name, at) = match QUOTED_NAME
+            .captures(trimmed)
+            .and_then(|c| c.get(1).or_else(|| c.get(2)).or_else(|| c.get(3)))
+        {
+            Some(m) => (m.as_str().to_string(), offset + lead + m.start()),
+            None => match resolve_key(trimmed, &constants, &keys) {
+                Some(name) => (name, offset + lead),
+                None => continue,
+            },
+        };
+        let line = content[..at].matches('\n').count();
+        let line_start = content[..at].rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        let default_value = wrapper
+            .default_arg
+            .and_then(|i| args.get(i))
+            .map(|(_, d)| d.trim().trim_matches(['"', '\'', '`']).to_string());
+
+        usages.push(EnvVarUsage {
+            name,
+            file_path: file_path.to_path_buf(),
+            line: line + 1,
+            column: at - line_start + 1,
+            language,
+            context: lines.get(line).map(|l| l.trim().to_string()),
+            required: if default_value.is_some() {
+                Some(false)
+            } else {
+                wrapper.required
+            },
+            default_value,
+            exposure: None,
+        });
+    }
+
+    usages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(language: Language, file: &str, content: &str) -> Vec<EnvVarUsage> {
+        let mut context = ScanContext::default();
+        collect_wrappers(language, content, Path::new(file), &mut context);
+        scan_wrapper_calls(language, content, Path::new(file), &context)
+    }
+
+    #[test]
+    fn test_javascript_wrappers() {
+        let content = r#"function requireEnv(name) {
+  const value = process.env[name];
+  if (!value) throw new Error(`Missing ${name}`);
+  return value;
+}
+const optionalEnv = (key: string, fallback = "") => process.env[key] ?? fallback;
+
+const db = requireEnv("DB_URL");
+const mode = optionalEnv(
+  "MODE",
+  "development",
+);
+"#;
+        let usages = scan(Language::JavaScript, "config.ts", content);
+        assert_eq!(usages.len(), 2);
+        assert_eq!(usages[0].name, "DB_URL");
+        assert_eq!((usages[0].line, usages[0].column), (8, 24));
+        assert_eq!(usages[0].required, Some(true));
+        assert_eq!(usages[1].name, "MODE");
+        assert_eq!(usages[1].line, 10);
+        assert_eq!(usages[1].default_value.as_deref(), Some("development"));
+    }
+
+    #[test]
+    fn test_python_wrapper_method() {
+        let content = r#"class Settings:
+    def env(self, key, default=None):
+        return os.environ.get(key, default)
+
+settings = Settings()
+url = settings.env("DATABASE_URL")
+"#;
+        let usages = scan(Language::Python, "settings.py", content);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "DATABASE_URL");
+        // `default` is passed through to environ.get, so the var is optional
+        assert_eq!(usages[0].required, Some(false));
+    }
+
+    #[test]
+    fn test_go_and_ruby_wrappers() {
+        let go = r#"func mustEnv(key string) string {
+	v, ok := os.LookupEnv(key)
+	if !ok {
+		log.Fatalf("missing %s", key)
+	}
+	return v
+}
+
+var port = mustEnv("PORT")
+"#;
+        let usages = scan(Language::Go, "main.go", go);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "PORT");
+        assert_eq!(usages[0].required, Some(true));
+
+        let ruby = r#"def env!(name)
+  ENV.fetch(name)
+end
+
+token = env! "API_TOKEN"
+"#;
+        let usages = scan(Language::Ruby, "boot.rb", ruby);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "API_TOKEN");
+    }
+
+    #[test]
+    fn test_declared_wrapper() {
+        let mut context = ScanContext::default();
+        context
+            .env_wrappers
+            .extend(EnvWrapper::from_config(&WrapperConfig {
+                name: "secret".to_string(),
+                languages: vec!["php".to_string()],
+                arg: 1,
+                default_arg: None,
+                required: Some(true),
+            }));
+
+        let content = r#"$key = Vault::secret('prod', 'STRIPE_KEY');"#;
+        let usages = scan_wrapper_calls(Language::Php, content, Path::new("a.php"), &context);
+        assert_eq!(usages.len(), 1);
+        assert_eq!(usages[0].name, "STRIPE_KEY");
+        assert_eq!(usages[0].required, Some(true));
+
+        let usages = scan_wrapper_calls(Language::Ruby, content, Path::new("a.rb"), &context);
+        assert!(usages.is_empty());
+    }
+
+    #[test]
+    fn test_dynamic_access_without_parameter_is_not_a_wrapper() {
+        let content = r#"function dump(prefix) {
+  const key = prefix + "_URL";
+  return process.env[key];
+}
+dump("DB");
+"#;
+        assert!(scan(Language::JavaScript, "a.js", content).is_empty());
+    }
+
+    #[test]
+    fn test_detected_wrapper_scope() {
+        let mut context = ScanContext::default();
+        let definition = "export function requireEnv(name) {\n  return process.env[name];\n}\n";
+        collect_wrappers(
+            Language::JavaScript,
+            definition,
+            Path::new("src/env.ts"),
+            &mut context,
+        );
+        let scan = |file: &str, content: &str| {
+            scan_wrapper_calls(Language::JavaScript, content, Path::new(file), &context)
+        };
+
+        let importer = "import { requireEnv } from './env';\nconst url = requireEnv('DB_URL');\n";
+        assert_eq!(scan("src/db.ts", importer).len(), 1);
+
+        // Another module's function of the same name
+        let unrelated = "const url = requireEnv('DB_URL');\n";
+        assert!(scan("scripts/seed.ts", unrelated).is_empty());
+
+        // A method on some other object, not the free function
+        let method = "import { requireEnv } from './env';\nconst v = form.requireEnv('DB_URL');\n";
+        assert!(scan("src/form.ts", method).is_empty());
+    }
+
+    #[test]
+    fn test_import_specifier_and_receivers() {
+        let mut context = ScanContext::default();
+        let definition = "export function requireEnv(name) {\n  return process.env[name];\n}\n";
+        collect_wrappers(
+            Language::TypeScript,
+            definition,
+            Path::new("src/lib/env.ts"),
+            &mut context,
+        );
+        let scan = |file: &str, content: &str| {
+            scan_wrapper_calls(Language::TypeScript, content, Path::new(file), &context)
+        };
+
+        // The word `env` in a SvelteKit import is not this module
+        let sveltekit = "import { env } from '$env/dynamic/private';\nconst v = requireEnv('A');\n";
+        assert!(scan("src/routes/page.ts", sveltekit).is_empty());
+
+        let namespace = "import * as env from '../lib/env';\nconst v = env.requireEnv('A');\n";
+        assert_eq!(scan("src/routes/page.ts", namespace).len(), 1);
+        let other = "import * as env from '../lib/env';\nconst v = other.requireEnv('A');\n";
+        assert!(scan("src/routes/page.ts", other).is_empty());
+    }
+
+    #[test]
+    fn test_method_receivers() {
+        let mut context = ScanContext::default();
+        let definition = r#"class Settings:
+    def env(self, key):
+        return os.environ[key]
+
+    def url(self):
+        return self.env("DATABASE_URL")
+"#;
+        let file = Path::new("app/settings.py");
+        collect_wrappers(Language::Python, definition, file, &mut context);
+        assert_eq!(context.env_wrappers[0].class.as_deref(), Some("Settings"));
+        let scan = |file: &str, content: &str| {
+            scan_wrapper_calls(Language::Python, content, Path::new(file), &context)
+        };
+
+        assert_eq!(scan("app/settings.py", definition).len(), 1);
+        let instance = "from app.settings import Settings\ns = Settings()\ns.env('A')\n";
+        assert_eq!(scan("app/main.py", instance).len(), 1);
+        let unrelated = "from app.settings import Settings\nform.env('A')\n";
+        assert!(scan("app/main.py", unrelated).is_empty());
+        let not_imported = "s = Settings()\ns.env('A')\n";
+        assert!(scan("tools/other.py", not_imported).is_empty());
+    }
+
+    #[test]
+    fn test_receiver() {
+        assert_eq!(call_receiver("const v = settings."), Some("settings"));
+        assert_eq!(call_receiver("Vault::"), Some("Vault"));
+        assert_eq!(call_receiver("$this->"), Some("$this"));
+        assert_eq!(call_receiver("const v = "), None);
+    }
+}
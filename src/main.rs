@@ -7,8 +7,12 @@ use env_audit::analysis::analyze;
 use env_audit::cli::{Cli, Commands, OutputFormat, ScanArgs};
 use env_audit::config::Config;
 use env_audit::output::{HtmlOutput, JsonOutput, MarkdownOutput, OutputFormatter, TerminalOutput};
-use env_audit::scanner::{parse_env_file, CodeScanner, FileWalker};
-use env_audit::types::{ScanReport, Severity};
+use env_audit::scanner::{
+    find_client_files, is_dev_vars_file, parse_deno_config, parse_dev_vars, parse_env_file,
+    parse_package_json, parse_public_prefixes, parse_wrangler_config, CodeScanner, FileWalker,
+    DENO_CONFIG_FILES, PACKAGE_JSON, WRANGLER_CONFIG,
+};
+use env_audit::types::{ClientApp, ClientFramework, ProjectInfo, ScanReport, Severity};
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -115,7 +119,7 @@ fn cmd_list(cli: &Cli, args: &env_audit::cli::ListArgs) -> Result<()> {
     if !args.defined {
         // Show used vars
         println!("\nUsed environment variables:\n");
-        let scanner = CodeScanner::new();
+        let scanner = CodeScanner::with_config(&config.scan);
         let source_files = walker.find_source_files()?;
         let usages = scanner.scan_files(&source_files);
 
@@ -209,19 +213,84 @@ fn run_scan(cli: &Cli) -> Result<ScanReport> {
     let walker = FileWalker::new(&cli.path, &config.scan);
 
     // Find and parse .env files
-    let env_files = walker.find_env_files(&config.scan.env_files)?;
+    let mut env_files = walker.find_env_files(&config.scan.env_files)?;
     let mut definitions = Vec::new();
     for env_file in &env_files {
         definitions.extend(parse_env_file(env_file)?);
     }
 
+    // Cloudflare Workers: wrangler.toml vars and bindings, .dev.vars secrets
+    if config.scan.workers {
+        for file in walker.find_files(|path| {
+            is_dev_vars_file(path) || path.file_name().is_some_and(|n| n == WRANGLER_CONFIG)
+        })? {
+            if is_dev_vars_file(&file) {
+                definitions.extend(parse_dev_vars(&file)?);
+            } else {
+                definitions.extend(parse_wrangler_config(&file)?);
+            }
+            env_files.push(file);
+        }
+    }
+
     // Find and scan source files
-    let source_files = walker.find_source_files()?;
-    let scanner = CodeScanner::new();
+    let scanner = CodeScanner::with_config(&config.scan);
+    let source_files: Vec<_> = walker
+        .find_source_files()?
+        .into_iter()
+        .filter(|path| scanner.supports_file(path))
+        .collect();
     let usages = scanner.scan_files(&source_files);
 
+    // Find Deno projects declaring --allow-env permissions
+    let mut project = ProjectInfo {
+        root: cli.path.clone(),
+        ..Default::default()
+    };
+    for deno_config in walker.find_files(|path| {
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| DENO_CONFIG_FILES.contains(&n))
+    })? {
+        project
+            .deno_permissions
+            .extend(parse_deno_config(&deno_config)?);
+    }
+
+    // Find client-side apps, from [client] frameworks or package.json
+    let package_jsons =
+        walker.find_files(|path| path.file_name().is_some_and(|n| n == PACKAGE_JSON))?;
+    for package_json in &package_jsons {
+        project
+            .public_prefixes
+            .extend(parse_public_prefixes(package_json)?);
+    }
+    if config.client.frameworks.is_empty() {
+        for package_json in &package_jsons {
+            project
+                .client_apps
+                .extend(parse_package_json(package_json)?);
+        }
+    } else {
+        project.client_apps.push(ClientApp {
+            root: cli.path.clone(),
+            frameworks: config
+                .client
+                .frameworks
+                .iter()
+                .filter_map(|name| ClientFramework::from_name(name))
+                .collect(),
+        });
+    }
+    project.client_files = find_client_files(
+        &cli.path,
+        &source_files,
+        &project.client_apps,
+        &config.client.paths,
+    )?;
+
     // Run analysis
-    let issues = analyze(&definitions, &usages, &config);
+    let issues = analyze(&definitions, &usages, &project, &config);
 
     // Build report
     let mut report = ScanReport::new();
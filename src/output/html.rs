@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use super::OutputFormatter;
-use crate::types::{IssueKind, ScanReport, Severity};
+use crate::types::{Issue, IssueKind, ScanReport, Severity};
 
 pub struct HtmlOutput;
 
@@ -25,6 +25,43 @@ impl HtmlOutput {
             Severity::Info => "Info",
         }
     }
+
+    /// The first three locations and how many more there are
+    fn location_list(issue: &Issue) -> String {
+        let locations: String = issue
+            .locations
+            .iter()
+            .take(3)
+            .map(|l| format!("<span class=\"location\">{}</span>", l))
+            .collect::<Vec<_>>()
+            .join("<br>");
+        if issue.locations.len() > 3 {
+            format!("{} (+{} more)", locations, issue.locations.len() - 3)
+        } else {
+            locations
+        }
+    }
+
+    /// Table of issues with their locations and suggestions
+    fn issue_table(issues: &[&Issue]) -> String {
+        let mut table = String::new();
+        table.push_str("    <table>\n");
+        table.push_str(
+            "        <tr><th>Severity</th><th>Variable</th><th>Location</th><th>Suggestion</th></tr>\n",
+        );
+        for issue in issues {
+            table.push_str(&format!(
+                "        <tr><td><span class=\"severity {}\">{}</span></td><td class=\"var-name\">{}</td><td>{}</td><td>{}</td></tr>\n",
+                Self::severity_class(issue.severity),
+                Self::severity_label(issue.severity),
+                issue.var_name,
+                Self::location_list(issue),
+                issue.suggestion.as_deref().unwrap_or("")
+            ));
+        }
+        table.push_str("    </table>\n");
+        table
+    }
 }
 
 impl Default for HtmlOutput {
@@ -140,6 +177,26 @@ impl OutputFormatter for HtmlOutput {
                 .iter()
                 .filter(|i| i.kind == IssueKind::InconsistentNaming)
                 .collect();
+            let permissions: Vec<_> = report
+                .issues
+                .iter()
+                .filter(|i| i.kind == IssueKind::EnvPermission)
+                .collect();
+            let client: Vec<_> = report
+                .issues
+                .iter()
+                .filter(|i| i.kind == IssueKind::MissingClientPrefix)
+                .collect();
+            let typos: Vec<_> = report
+                .issues
+                .iter()
+                .filter(|i| i.kind == IssueKind::PossibleTypo)
+                .collect();
+            let secrets: Vec<_> = report
+                .issues
+                .iter()
+                .filter(|i| i.kind == IssueKind::ExposedSecret)
+                .collect();
 
             // Missing vars table
             if !missing.is_empty() {
@@ -149,19 +206,12 @@ impl OutputFormatter for HtmlOutput {
                     "        <tr><th>Severity</th><th>Variable</th><th>Used In</th></tr>\n",
                 );
                 for issue in &missing {
-                    let locations: String = issue
-                        .locations
-                        .iter()
-                        .take(3)
-                        .map(|l| format!("<span class=\"location\">{}</span>", l))
-                        .collect::<Vec<_>>()
-                        .join("<br>");
                     output.push_str(&format!(
                         "        <tr><td><span class=\"severity {}\">{}</span></td><td class=\"var-name\">{}</td><td>{}</td></tr>\n",
                         Self::severity_class(issue.severity),
                         Self::severity_label(issue.severity),
                         issue.var_name,
-                        locations
+                        Self::location_list(issue)
                     ));
                 }
                 output.push_str("    </table>\n");
@@ -192,6 +242,30 @@ impl OutputFormatter for HtmlOutput {
                 output.push_str("    </table>\n");
             }
 
+            // Possible typos table
+            if !typos.is_empty() {
+                output.push_str("    <h2>Possible Typos</h2>\n");
+                output.push_str(&Self::issue_table(&typos));
+            }
+
+            // Deno --allow-env permissions table
+            if !permissions.is_empty() {
+                output.push_str("    <h2>Env Permissions</h2>\n");
+                output.push_str(&Self::issue_table(&permissions));
+            }
+
+            // Client prefix table
+            if !client.is_empty() {
+                output.push_str("    <h2>Client Environment Variables</h2>\n");
+                output.push_str(&Self::issue_table(&client));
+            }
+
+            // Exposed secrets table
+            if !secrets.is_empty() {
+                output.push_str("    <h2>Exposed Secrets</h2>\n");
+                output.push_str(&Self::issue_table(&secrets));
+            }
+
             // Naming issues table
             if !naming.is_empty() {
                 output.push_str("    <h2>Naming Convention Issues</h2>\n");
@@ -1,7 +1,7 @@
 use anyhow::Result;
 
 use super::OutputFormatter;
-use crate::types::{IssueKind, ScanReport, Severity};
+use crate::types::{Issue, IssueKind, ScanReport, Severity};
 
 pub struct MarkdownOutput;
 
@@ -17,6 +17,44 @@ impl MarkdownOutput {
             Severity::Info => ":information_source:",
         }
     }
+
+    /// The first three locations and how many more there are
+    fn location_list(issue: &Issue) -> String {
+        let locations: Vec<String> = issue
+            .locations
+            .iter()
+            .take(3)
+            .map(|l| format!("`{}`", l))
+            .collect();
+        if issue.locations.len() > 3 {
+            format!(
+                "{} (+{} more)",
+                locations.join(", "),
+                issue.locations.len() - 3
+            )
+        } else {
+            locations.join(", ")
+        }
+    }
+
+    /// Table of issues with their locations and suggestions
+    fn issue_table(issues: &[&Issue]) -> String {
+        let mut table = String::new();
+        table.push_str("| | Variable | Location | Suggestion |\n");
+        table.push_str("|---|----------|----------|------------|\n");
+
+        for issue in issues {
+            table.push_str(&format!(
+                "| {} | `{}` | {} | {} |\n",
+                Self::severity_emoji(issue.severity),
+                issue.var_name,
+                Self::location_list(issue),
+                issue.suggestion.as_deref().unwrap_or("")
+            ));
+        }
+        table.push('\n');
+        table
+    }
 }
 
 impl Default for MarkdownOutput {
@@ -90,6 +128,26 @@ impl OutputFormatter for MarkdownOutput {
             .iter()
             .filter(|i| i.kind == IssueKind::InconsistentNaming)
             .collect();
+        let permissions: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::EnvPermission)
+            .collect();
+        let client: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::MissingClientPrefix)
+            .collect();
+        let typos: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::PossibleTypo)
+            .collect();
+        let secrets: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::ExposedSecret)
+            .collect();
 
         // Missing env vars
         if !missing.is_empty() {
@@ -101,27 +159,11 @@ impl OutputFormatter for MarkdownOutput {
             output.push_str("|---|----------|----------|\n");
 
             for issue in &missing {
-                let locations: Vec<String> = issue
-                    .locations
-                    .iter()
-                    .take(3)
-                    .map(|l| format!("`{}`", l))
-                    .collect();
-                let location_str = if issue.locations.len() > 3 {
-                    format!(
-                        "{} (+{} more)",
-                        locations.join(", "),
-                        issue.locations.len() - 3
-                    )
-                } else {
-                    locations.join(", ")
-                };
-
                 output.push_str(&format!(
                     "| {} | `{}` | {} |\n",
                     Self::severity_emoji(issue.severity),
                     issue.var_name,
-                    location_str
+                    Self::location_list(issue)
                 ));
             }
             output.push('\n');
@@ -150,6 +192,42 @@ impl OutputFormatter for MarkdownOutput {
             output.push('\n');
         }
 
+        // Missing vars that look like misspelt definitions
+        if !typos.is_empty() {
+            output.push_str("## Possible Typos\n\n");
+            output.push_str(
+                "These variables are used in code but not defined, while a similar name is defined but never used.\n\n",
+            );
+            output.push_str(&Self::issue_table(&typos));
+        }
+
+        // Deno --allow-env permissions
+        if !permissions.is_empty() {
+            output.push_str("## Env Permissions\n\n");
+            output.push_str(
+                "These reads are not granted, or these grants are not needed, by `--allow-env` in `deno.json`.\n\n",
+            );
+            output.push_str(&Self::issue_table(&permissions));
+        }
+
+        // Vars client code reads without the public prefix
+        if !client.is_empty() {
+            output.push_str("## Client Environment Variables\n\n");
+            output.push_str(
+                "These variables are read in browser code but lack the prefix the framework needs to expose them.\n\n",
+            );
+            output.push_str(&Self::issue_table(&client));
+        }
+
+        // Secrets behind public prefixes
+        if !secrets.is_empty() {
+            output.push_str("## Exposed Secrets\n\n");
+            output.push_str(
+                "These variables look like secrets but have a public prefix, so they are bundled into client code.\n\n",
+            );
+            output.push_str(&Self::issue_table(&secrets));
+        }
+
         // Naming convention issues
         if !naming.is_empty() {
             output.push_str("## Naming Convention Issues\n\n");
@@ -3,7 +3,7 @@ use colored::Colorize;
 use comfy_table::{Cell, Color, ContentArrangement, Table};
 
 use super::OutputFormatter;
-use crate::types::{IssueKind, ScanReport, Severity};
+use crate::types::{Issue, IssueKind, ScanReport, Severity};
 
 pub struct TerminalOutput {
     pub no_color: bool,
@@ -36,6 +36,48 @@ impl TerminalOutput {
             Severity::Info => "i",
         }
     }
+
+    /// The first three locations, one per line, and how many more there are
+    fn location_list(issue: &Issue) -> String {
+        let locations: Vec<String> = issue
+            .locations
+            .iter()
+            .take(3)
+            .map(|l| l.to_string())
+            .collect();
+        if issue.locations.len() > 3 {
+            format!(
+                "{} (+{} more)",
+                locations.join("\n"),
+                issue.locations.len() - 3
+            )
+        } else {
+            locations.join("\n")
+        }
+    }
+
+    /// Table of issues with their locations and suggestions
+    fn issue_table(&self, issues: &[&Issue]) -> Table {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("").fg(Color::White),
+            Cell::new("Variable").fg(Color::White),
+            Cell::new("Location").fg(Color::White),
+            Cell::new("Suggestion").fg(Color::White),
+        ]);
+
+        for issue in issues {
+            table.add_row(vec![
+                Cell::new(self.severity_symbol(issue.severity))
+                    .fg(self.severity_color(issue.severity)),
+                Cell::new(&issue.var_name),
+                Cell::new(Self::location_list(issue)),
+                Cell::new(issue.suggestion.as_deref().unwrap_or("")),
+            ]);
+        }
+        table
+    }
 }
 
 impl OutputFormatter for TerminalOutput {
@@ -76,6 +118,26 @@ impl OutputFormatter for TerminalOutput {
             .iter()
             .filter(|i| i.kind == IssueKind::InconsistentNaming)
             .collect();
+        let permissions: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::EnvPermission)
+            .collect();
+        let client: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::MissingClientPrefix)
+            .collect();
+        let typos: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::PossibleTypo)
+            .collect();
+        let secrets: Vec<_> = report
+            .issues
+            .iter()
+            .filter(|i| i.kind == IssueKind::ExposedSecret)
+            .collect();
 
         // Missing env vars
         if !missing.is_empty() {
@@ -94,27 +156,11 @@ impl OutputFormatter for TerminalOutput {
             ]);
 
             for issue in &missing {
-                let locations: Vec<String> = issue
-                    .locations
-                    .iter()
-                    .take(3)
-                    .map(|l| l.to_string())
-                    .collect();
-                let location_str = if issue.locations.len() > 3 {
-                    format!(
-                        "{} (+{} more)",
-                        locations.join("\n"),
-                        issue.locations.len() - 3
-                    )
-                } else {
-                    locations.join("\n")
-                };
-
                 table.add_row(vec![
                     Cell::new(self.severity_symbol(issue.severity))
                         .fg(self.severity_color(issue.severity)),
                     Cell::new(&issue.var_name),
-                    Cell::new(location_str),
+                    Cell::new(Self::location_list(issue)),
                 ]);
             }
             output.push_str(&format!("{}\n\n", table));
@@ -150,6 +196,46 @@ impl OutputFormatter for TerminalOutput {
             output.push_str(&format!("{}\n\n", table));
         }
 
+        // Missing vars that look like misspelt definitions
+        if !typos.is_empty() {
+            output.push_str(&format!(
+                "{} ({})\n",
+                "POSSIBLE TYPOS".red().bold(),
+                typos.len()
+            ));
+            output.push_str(&format!("{}\n\n", self.issue_table(&typos)));
+        }
+
+        // Deno --allow-env permissions
+        if !permissions.is_empty() {
+            output.push_str(&format!(
+                "{} ({})\n",
+                "ENV PERMISSIONS".red().bold(),
+                permissions.len()
+            ));
+            output.push_str(&format!("{}\n\n", self.issue_table(&permissions)));
+        }
+
+        // Vars client code reads without the public prefix
+        if !client.is_empty() {
+            output.push_str(&format!(
+                "{} ({})\n",
+                "CLIENT ENV VARS".red().bold(),
+                client.len()
+            ));
+            output.push_str(&format!("{}\n\n", self.issue_table(&client)));
+        }
+
+        // Secrets behind public prefixes
+        if !secrets.is_empty() {
+            output.push_str(&format!(
+                "{} ({})\n",
+                "EXPOSED SECRETS".red().bold(),
+                secrets.len()
+            ));
+            output.push_str(&format!("{}\n\n", self.issue_table(&secrets)));
+        }
+
         // Naming convention issues
         if !naming.is_empty() {
             output.push_str(&format!(
@@ -14,6 +14,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             ],
             preferred: "DATABASE_URL".to_string(),
             severity: Severity::Warning,
+            regex: false,
         },
         NamingRule {
             name: "redis-url".to_string(),
@@ -21,6 +22,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["REDIS_HOST".to_string(), "REDIS_CONNECTION".to_string()],
             preferred: "REDIS_URL".to_string(),
             severity: Severity::Warning,
+            regex: false,
         },
         NamingRule {
             name: "api-key".to_string(),
@@ -28,6 +30,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["APIKEY".to_string(), "API_SECRET".to_string()],
             preferred: "API_KEY".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
         NamingRule {
             name: "secret-key".to_string(),
@@ -35,6 +38,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["SECRET".to_string(), "APP_SECRET".to_string()],
             preferred: "SECRET_KEY".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
         NamingRule {
             name: "port".to_string(),
@@ -46,6 +50,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             ],
             preferred: "PORT".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
         NamingRule {
             name: "log-level".to_string(),
@@ -53,6 +58,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["LOGLEVEL".to_string(), "LOGGING_LEVEL".to_string()],
             preferred: "LOG_LEVEL".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
         NamingRule {
             name: "aws-region".to_string(),
@@ -60,6 +66,7 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["REGION".to_string(), "AMAZON_REGION".to_string()],
             preferred: "AWS_REGION".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
         NamingRule {
             name: "jwt-secret".to_string(),
@@ -67,6 +74,25 @@ pub fn get_builtin_rules() -> Vec<NamingRule> {
             alternatives: vec!["JWT_KEY".to_string(), "TOKEN_SECRET".to_string()],
             preferred: "JWT_SECRET".to_string(),
             severity: Severity::Info,
+            regex: false,
         },
     ]
 }
+
+/// Returns the built-in synonym groups; the first word of each wins ties
+pub fn get_builtin_synonyms() -> Vec<Vec<String>> {
+    [
+        &["DATABASE", "DB"][..],
+        &["PASSWORD", "PASS", "PWD", "PASSWD"],
+        &["CONNECTION", "CONN"],
+        &["ADDRESS", "ADDR"],
+        &["HOST", "HOSTNAME"],
+        &["CONFIG", "CONF", "CFG"],
+        &["DIRECTORY", "DIR"],
+        &["ENVIRONMENT", "ENV"],
+        &["REPOSITORY", "REPO"],
+    ]
+    .iter()
+    .map(|group| group.iter().map(|w| w.to_string()).collect())
+    .collect()
+}
@@ -0,0 +1,232 @@
+use std::path::{Component, Path};
+
+use crate::config::{ConventionConfig, PrefixRule};
+
+/// How a name breaks the configured conventions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConventionViolation {
+    /// Human-readable descriptions of each broken convention
+    pub problems: Vec<String>,
+    /// The name rewritten to follow the conventions it can be fixed for
+    pub rewrite: String,
+}
+
+/// Check a name against the conventions. `files` are the files the name is
+/// defined or used in, relative to the scan root, which select the
+/// directory-specific prefix rules.
+pub fn check_conventions(
+    name: &str,
+    files: &[&Path],
+    conventions: &ConventionConfig,
+) -> Option<ConventionViolation> {
+    let mut problems = Vec::new();
+    let mut rewrite = name.to_string();
+
+    if conventions.screaming_snake && !is_screaming_snake(name) {
+        problems.push("not SCREAMING_SNAKE_CASE".to_string());
+        rewrite = to_screaming_snake(&rewrite);
+    }
+
+    if conventions.no_double_underscore && name.contains("__") {
+        problems.push("contains a double underscore".to_string());
+        while rewrite.contains("__") {
+            rewrite = rewrite.replace("__", "_");
+        }
+    }
+
+    let upper = name.to_uppercase();
+    let banned: Vec<&String> = conventions
+        .banned_words
+        .iter()
+        .filter(|word| upper.split('_').any(|w| w == word.to_uppercase()))
+        .collect();
+    if !banned.is_empty() {
+        let words: Vec<&str> = banned.iter().map(|w| w.as_str()).collect();
+        problems.push(format!("contains banned word {}", words.join(", ")));
+        let kept: Vec<&str> = rewrite
+            .split('_')
+            .filter(|w| !banned.iter().any(|b| b.eq_ignore_ascii_case(w)))
+            .collect();
+        // A name made only of banned words has nothing left to suggest
+        if kept.iter().any(|w| !w.is_empty()) {
+            rewrite = kept.join("_");
+        }
+    }
+
+    // Every directory the name appears in must allow its prefix
+    let mut prefix_rules: Vec<&PrefixRule> = Vec::new();
+    for file in files {
+        if let Some(rule) = prefix_rule_for(&conventions.prefixes, file) {
+            if !prefix_rules.iter().any(|r| std::ptr::eq(*r, rule)) {
+                prefix_rules.push(rule);
+            }
+        }
+    }
+    for rule in prefix_rules {
+        if rule.allowed.iter().any(|p| name.starts_with(p.as_str())) {
+            continue;
+        }
+        let scope = rule
+            .path
+            .as_deref()
+            .map(|p| format!(" in {}", p))
+            .unwrap_or_default();
+        problems.push(format!(
+            "missing an allowed prefix{} ({})",
+            scope,
+            rule.allowed.join(", ")
+        ));
+        if let Some(prefix) = rule.allowed.first() {
+            if !rewrite.starts_with(prefix.as_str()) {
+                rewrite = format!("{}{}", prefix, rewrite);
+            }
+        }
+    }
+
+    if conventions.no_leading_digit && name.starts_with(|c: char| c.is_ascii_digit()) {
+        problems.push("starts with a digit".to_string());
+        if rewrite.starts_with(|c: char| c.is_ascii_digit()) {
+            rewrite.insert(0, '_');
+        }
+    }
+
+    if let Some(max) = conventions.max_length {
+        if name.len() > max {
+            problems.push(format!("longer than {} characters", max));
+        }
+    }
+
+    (!problems.is_empty()).then_some(ConventionViolation { problems, rewrite })
+}
+
+fn is_screaming_snake(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `apiBaseUrl` -> `API_BASE_URL`, `db-host` -> `DB_HOST`
+fn to_screaming_snake(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            out.push('_');
+            continue;
+        }
+        if i > 0 && c.is_ascii_uppercase() {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                out.push('_');
+            }
+        }
+        out.push(c.to_ascii_uppercase());
+    }
+    out
+}
+
+/// The most specific prefix rule covering a file; rules without a path
+/// apply everywhere, others to files under that directory of the scan root
+fn prefix_rule_for<'a>(rules: &'a [PrefixRule], file: &Path) -> Option<&'a PrefixRule> {
+    rules
+        .iter()
+        .filter_map(|rule| match &rule.path {
+            None => Some((rule, 0)),
+            Some(dir) => {
+                let dir = normal_components(Path::new(dir));
+                normal_components(file)
+                    .starts_with(&dir)
+                    .then_some((rule, dir.len()))
+            }
+        })
+        .max_by_key(|(_, depth)| *depth)
+        .map(|(rule, _)| rule)
+}
+
+fn normal_components(path: &Path) -> Vec<&std::ffi::OsStr> {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conventions() -> ConventionConfig {
+        ConventionConfig {
+            screaming_snake: true,
+            banned_words: vec!["TEMP".to_string()],
+            max_length: Some(20),
+            no_leading_digit: true,
+            no_double_underscore: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_style_rewrites() {
+        let conventions = conventions();
+        let check = |name: &str| check_conventions(name, &[], &conventions);
+
+        assert_eq!(check("API_BASE_URL"), None);
+        assert_eq!(check("apiBaseUrl").unwrap().rewrite, "API_BASE_URL");
+        assert_eq!(check("CACHE__DIR").unwrap().rewrite, "CACHE_DIR");
+        assert_eq!(check("TEMP_UPLOAD_DIR").unwrap().rewrite, "UPLOAD_DIR");
+        assert_eq!(check("2FA_ISSUER").unwrap().rewrite, "_2FA_ISSUER");
+        assert_eq!(check("TEMP").unwrap().rewrite, "TEMP");
+
+        let long = check("SOME_EXTREMELY_LONG_VARIABLE").unwrap();
+        assert_eq!(long.problems, vec!["longer than 20 characters"]);
+        assert_eq!(long.rewrite, "SOME_EXTREMELY_LONG_VARIABLE");
+    }
+
+    #[test]
+    fn test_prefixes_per_path() {
+        let conventions = ConventionConfig {
+            prefixes: vec![
+                PrefixRule {
+                    path: None,
+                    allowed: vec!["APP_".to_string()],
+                },
+                PrefixRule {
+                    path: Some("services/billing".to_string()),
+                    allowed: vec!["BILLING_".to_string(), "STRIPE_".to_string()],
+                },
+            ],
+            ..Default::default()
+        };
+        let billing = Path::new("./services/billing/src/charge.ts");
+        let web = Path::new("./web/src/main.ts");
+
+        assert_eq!(
+            check_conventions("STRIPE_KEY", &[billing], &conventions),
+            None
+        );
+        assert_eq!(check_conventions("APP_PORT", &[web], &conventions), None);
+
+        let violation = check_conventions("WEBHOOK_SECRET", &[billing], &conventions).unwrap();
+        assert_eq!(
+            violation.problems,
+            vec!["missing an allowed prefix in services/billing (BILLING_, STRIPE_)"]
+        );
+        assert_eq!(violation.rewrite, "BILLING_WEBHOOK_SECRET");
+
+        // Used in both places, so both rules apply
+        let both = check_conventions("STRIPE_KEY", &[billing, web], &conventions).unwrap();
+        assert_eq!(both.rewrite, "APP_STRIPE_KEY");
+
+        // The path is matched from the scan root, not anywhere in the file path
+        let vendored = Path::new("vendor/services/billing/charge.ts");
+        assert_eq!(
+            check_conventions("APP_PORT", &[vendored], &conventions),
+            None
+        );
+    }
+}
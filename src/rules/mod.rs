@@ -1,6 +1,8 @@
 mod builtin;
+mod conventions;
 
-pub use builtin::get_builtin_rules;
+pub use builtin::{get_builtin_rules, get_builtin_synonyms};
+pub use conventions::{check_conventions, ConventionViolation};
 
 use crate::config::Config;
 use crate::types::Severity;
@@ -13,6 +15,8 @@ pub struct NamingRule {
     pub alternatives: Vec<String>,
     pub preferred: String,
     pub severity: Severity,
+    /// `alternatives` are regexes and `preferred` may reference captures
+    pub regex: bool,
 }
 
 /// Get all naming rules (built-in + custom from config)
@@ -32,8 +36,40 @@ pub fn get_all_rules(config: &Config) -> Vec<NamingRule> {
             alternatives: custom.alternatives.clone(),
             preferred: custom.preferred.clone(),
             severity: custom.severity_level(),
+            regex: custom.regex,
         });
     }
 
     rules
 }
+
+/// Get all synonym groups (built-in + `[naming] synonyms` from config).
+/// Groups sharing a word are merged; config groups come first, so their
+/// first word wins ties between equally common spellings.
+pub fn get_all_synonyms(config: &Config) -> Vec<Vec<String>> {
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let builtin = if config.naming.builtin_synonyms {
+        get_builtin_synonyms()
+    } else {
+        Vec::new()
+    };
+
+    for group in config.naming.synonyms.iter().chain(&builtin) {
+        let words: Vec<String> = group.iter().map(|w| w.to_uppercase()).collect();
+        match groups
+            .iter_mut()
+            .find(|existing| existing.iter().any(|w| words.contains(w)))
+        {
+            Some(existing) => {
+                for word in words {
+                    if !existing.contains(&word) {
+                        existing.push(word);
+                    }
+                }
+            }
+            None => groups.push(words),
+        }
+    }
+
+    groups
+}
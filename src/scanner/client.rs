@@ -0,0 +1,176 @@
+use anyhow::{Context, Result};
+use ignore::overrides::OverrideBuilder;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::types::{nearest_client_app, ClientApp, ClientFramework};
+
+/// npm manifest that client apps are detected from
+pub const PACKAGE_JSON: &str = "package.json";
+
+/// Detect the client-side frameworks a package.json depends on. Returns None
+/// when it uses none of them, or is not valid JSON.
+pub fn parse_package_json(path: &Path) -> Result<Option<ClientApp>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read package.json: {}", path.display()))?;
+    let root = path.parent().unwrap_or(Path::new("")).to_path_buf();
+    Ok(detect_frameworks(&content).map(|frameworks| ClientApp { root, frameworks }))
+}
+
+/// Packages whose apps expose vars with a generic prefix to the browser
+const PREFIX_PACKAGES: &[(&str, &str)] = &[
+    ("@sveltejs/kit", "PUBLIC_"),
+    ("astro", "PUBLIC_"),
+    ("gatsby", "GATSBY_"),
+    ("expo", "EXPO_PUBLIC_"),
+];
+
+/// The generic public prefixes, such as `PUBLIC_` for SvelteKit, used by the
+/// frameworks a package.json depends on
+pub fn parse_public_prefixes(path: &Path) -> Result<Vec<&'static str>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read package.json: {}", path.display()))?;
+    Ok(detect_public_prefixes(&content))
+}
+
+fn detect_public_prefixes(content: &str) -> Vec<&'static str> {
+    let Ok(manifest) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+    PREFIX_PACKAGES
+        .iter()
+        .filter(|(package, _)| depends_on(&manifest, package))
+        .map(|(_, prefix)| *prefix)
+        .collect()
+}
+
+fn depends_on(manifest: &serde_json::Value, package: &str) -> bool {
+    ["dependencies", "devDependencies"]
+        .iter()
+        .any(|section| manifest[section].get(package).is_some())
+}
+
+fn detect_frameworks(content: &str) -> Option<Vec<ClientFramework>> {
+    let manifest: serde_json::Value = serde_json::from_str(content).ok()?;
+
+    let frameworks: Vec<ClientFramework> = [
+        ("vite", ClientFramework::Vite),
+        ("react-scripts", ClientFramework::CreateReactApp),
+        ("next", ClientFramework::Next),
+    ]
+    .into_iter()
+    .filter(|(package, _)| depends_on(&manifest, package))
+    .map(|(_, framework)| framework)
+    .collect();
+
+    (!frameworks.is_empty()).then_some(frameworks)
+}
+
+/// Source files whose code ships to the browser: files matching the
+/// `[client] paths` globs, everything under `src/` in Create React App apps,
+/// and Next.js modules starting with a "use client" directive
+pub fn find_client_files(
+    root: &Path,
+    source_files: &[PathBuf],
+    apps: &[ClientApp],
+    globs: &[String],
+) -> Result<HashSet<PathBuf>> {
+    let mut builder = OverrideBuilder::new(root);
+    for glob in globs {
+        builder
+            .add(glob)
+            .with_context(|| format!("Invalid client path glob: {}", glob))?;
+    }
+    let overrides = builder
+        .build()
+        .context("Failed to build client path globs")?;
+
+    let mut client_files = HashSet::new();
+    for file in source_files {
+        if overrides.matched(file, false).is_whitelist() {
+            client_files.insert(file.clone());
+            continue;
+        }
+        let Some(app) = nearest_client_app(apps, file) else {
+            continue;
+        };
+        let is_cra_source = app.frameworks.contains(&ClientFramework::CreateReactApp)
+            && file.starts_with(app.root.join("src"));
+        if is_cra_source
+            || (app.frameworks.contains(&ClientFramework::Next)
+                && std::fs::read_to_string(file).is_ok_and(|content| has_use_client(&content)))
+        {
+            client_files.insert(file.clone());
+        }
+    }
+
+    Ok(client_files)
+}
+
+/// True when the module's directive prologue contains "use client"
+fn has_use_client(content: &str) -> bool {
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        let directive = line.trim_end_matches(';');
+        if directive == "\"use client\"" || directive == "'use client'" {
+            return true;
+        }
+        // Other directives such as "use strict" may come first
+        if !(directive.starts_with('"') || directive.starts_with('\'')) {
+            return false;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_frameworks() {
+        let content = r#"{
+  "dependencies": { "react": "^18.2.0", "next": "14.1.0" },
+  "devDependencies": { "vite": "^5.0.0" }
+}"#;
+        assert_eq!(
+            detect_frameworks(content),
+            Some(vec![ClientFramework::Vite, ClientFramework::Next])
+        );
+
+        let cra = r#"{ "dependencies": { "react-scripts": "5.0.1" } }"#;
+        assert_eq!(
+            detect_frameworks(cra),
+            Some(vec![ClientFramework::CreateReactApp])
+        );
+
+        assert_eq!(detect_frameworks(r#"{ "dependencies": {} }"#), None);
+        assert_eq!(detect_frameworks("not json"), None);
+    }
+
+    #[test]
+    fn test_detect_public_prefixes() {
+        let content = r#"{
+  "dependencies": { "@sveltejs/kit": "^2.0.0" },
+  "devDependencies": { "gatsby": "^5.0.0", "vite": "^5.0.0" }
+}"#;
+        assert_eq!(detect_public_prefixes(content), vec!["PUBLIC_", "GATSBY_"]);
+        assert!(detect_public_prefixes(r#"{ "dependencies": { "next": "14" } }"#).is_empty());
+        assert!(detect_public_prefixes("not json").is_empty());
+    }
+
+    #[test]
+    fn test_use_client_directive() {
+        assert!(has_use_client(
+            "'use client';\n\nexport default function Page() {}"
+        ));
+        assert!(has_use_client(
+            "// Button\n\"use strict\"\n\"use client\"\n"
+        ));
+        assert!(!has_use_client("import x from 'y';\n'use client';"));
+        assert!(!has_use_client("\"use server\";\n"));
+    }
+}
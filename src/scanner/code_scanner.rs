@@ -1,39 +1,181 @@
 use anyhow::Result;
 use rayon::prelude::*;
+use regex::Regex;
 use std::path::{Path, PathBuf};
 
-use crate::languages::LanguageRegistry;
-use crate::types::EnvVarUsage;
+use crate::config::ScanConfig;
+use crate::languages::{
+    collect_wrappers, file_constants, scan_constant_keys, scan_wrapper_calls, EnvWrapper,
+    LanguageRegistry, LanguageScanner, ScanContext,
+};
+use crate::types::{EnvVarUsage, Language};
 
 /// Scans source code files for environment variable usage
 pub struct CodeScanner {
     registry: LanguageRegistry,
+    /// Accepted var names; usages with other names are dropped
+    name_pattern: Option<Regex>,
+    /// Env wrapper functions declared in config
+    wrappers: Vec<EnvWrapper>,
+    /// Path suffixes of files holding env key constants
+    key_modules: Vec<PathBuf>,
 }
 
 impl CodeScanner {
     pub fn new() -> Self {
         Self {
             registry: LanguageRegistry::new(),
+            name_pattern: None,
+            wrappers: Vec::new(),
+            key_modules: Vec::new(),
+        }
+    }
+
+    /// Create a scanner that uses a custom language registry
+    pub fn with_registry(registry: LanguageRegistry) -> Self {
+        Self {
+            registry,
+            name_pattern: None,
+            wrappers: Vec::new(),
+            key_modules: Vec::new(),
+        }
+    }
+
+    /// Returns true if any registered scanner handles this file
+    pub fn supports_file(&self, path: &Path) -> bool {
+        self.registry.supports_file(path)
+    }
+
+    /// Create a scanner configured from the `[scan]` section
+    pub fn with_config(config: &ScanConfig) -> Self {
+        Self {
+            registry: LanguageRegistry::from_config(config),
+            name_pattern: Regex::new(&config.name_pattern).ok(),
+            wrappers: config
+                .wrappers
+                .iter()
+                .flat_map(EnvWrapper::from_config)
+                .collect(),
+            key_modules: config
+                .key_modules
+                .iter()
+                .map(|m| PathBuf::from(m.trim_start_matches("./").replace('\\', "/")))
+                .collect(),
         }
     }
 
     /// Scan a single file for env var usages
     pub fn scan_file(&self, path: &Path) -> Result<Vec<EnvVarUsage>> {
+        self.scan_file_with_context(path, &ScanContext::default())
+    }
+
+    /// Scan a single file using facts collected from the rest of the project
+    pub fn scan_file_with_context(
+        &self,
+        path: &Path,
+        context: &ScanContext,
+    ) -> Result<Vec<EnvVarUsage>> {
+        let scanners = self.registry.get_scanners_for_file(path);
+        if scanners.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let content = std::fs::read_to_string(path)?;
 
-        let scanner = match self.registry.get_scanner_for_file(path) {
-            Some(s) => s,
-            None => return Ok(Vec::new()),
-        };
+        let mut usages: Vec<EnvVarUsage> = scanners
+            .iter()
+            .flat_map(|scanner| scanner.scan_with_context(&content, path, context))
+            .collect();
+
+        // Calls to env wrappers and reads keyed by constants, unless a
+        // scanner already reported them
+        for language in Self::languages(&scanners) {
+            let resolved = scan_wrapper_calls(language, &content, path, context)
+                .into_iter()
+                .chain(scan_constant_keys(language, &content, path, context));
+            for usage in resolved {
+                if !usages
+                    .iter()
+                    .any(|u| u.name == usage.name && u.line == usage.line)
+                {
+                    usages.push(usage);
+                }
+            }
+        }
+
+        Ok(usages
+            .into_iter()
+            .filter(|usage| {
+                self.name_pattern
+                    .as_ref()
+                    .map(|re| re.is_match(&usage.name))
+                    .unwrap_or(true)
+            })
+            .collect())
+    }
+
+    /// Collect project-wide facts from all files in parallel
+    pub fn collect_context(&self, files: &[PathBuf]) -> ScanContext {
+        let mut context = files
+            .par_iter()
+            .fold(ScanContext::default, |mut context, path| {
+                let scanners = self.registry.get_scanners_for_file(path);
+                if scanners.is_empty() {
+                    return context;
+                }
+                if let Ok(content) = std::fs::read_to_string(path) {
+                    for scanner in &scanners {
+                        scanner.collect_context(&content, path, &mut context);
+                    }
+                    let key_module = self.is_key_module(path);
+                    for language in Self::languages(&scanners) {
+                        collect_wrappers(language, &content, path, &mut context);
+                        if key_module {
+                            context
+                                .key_constants
+                                .entry(path.clone())
+                                .or_default()
+                                .extend(file_constants(language, &content));
+                        }
+                    }
+                }
+                context
+            })
+            .reduce(ScanContext::default, ScanContext::merge);
+
+        // Keep wrappers defined in several files in a stable order, whatever
+        // way the files were split across threads
+        context
+            .env_wrappers
+            .sort_by(|a, b| (&a.file, &a.name).cmp(&(&b.file, &b.name)));
+        context.env_wrappers.extend(self.wrappers.iter().cloned());
+        context
+    }
 
-        Ok(scanner.scan(&content, path))
+    /// Whether the path ends with one of the key modules, whole components
+    /// only, so `keys.ts` does not match `apikeys.ts`
+    fn is_key_module(&self, path: &Path) -> bool {
+        self.key_modules.iter().any(|m| path.ends_with(m))
+    }
+
+    /// Distinct languages of the scanners handling a file
+    fn languages(scanners: &[&dyn LanguageScanner]) -> Vec<Language> {
+        let mut languages = Vec::new();
+        for scanner in scanners {
+            if !languages.contains(&scanner.language()) {
+                languages.push(scanner.language());
+            }
+        }
+        languages
     }
 
     /// Scan multiple files in parallel
     pub fn scan_files(&self, files: &[PathBuf]) -> Vec<EnvVarUsage> {
+        let context = self.collect_context(files);
+
         files
             .par_iter()
-            .filter_map(|path| self.scan_file(path).ok())
+            .filter_map(|path| self.scan_file_with_context(path, &context).ok())
             .flatten()
             .collect()
     }
@@ -44,3 +186,19 @@ impl Default for CodeScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_modules_match_whole_components() {
+        let scanner = CodeScanner::with_config(&ScanConfig {
+            key_modules: vec!["./config/keys.ts".to_string()],
+            ..Default::default()
+        });
+        assert!(scanner.is_key_module(Path::new("./web/src/config/keys.ts")));
+        assert!(!scanner.is_key_module(Path::new("./web/src/config/apikeys.ts")));
+        assert!(!scanner.is_key_module(Path::new("./web/src/myconfig/keys.ts")));
+    }
+}
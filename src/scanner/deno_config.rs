@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use crate::types::{DenoPermissions, EnvVarDefinition};
+
+/// Config file names Deno picks up
+pub const DENO_CONFIG_FILES: &[&str] = &["deno.json", "deno.jsonc"];
+
+// `--allow-env`, `--allow-env=A,B` or `-A` / `--allow-all` in a task command
+static ALLOW_ENV: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|[\s"'])(?:--allow-env(?:=(?P<names>[^\s"']*))?|--allow-all|-A)(?:[\s"']|$)"#)
+        .unwrap()
+});
+
+/// Parse the `--allow-env` permissions from a deno.json file. Returns None
+/// when the file does not mention env permissions at all.
+pub fn parse_deno_config(path: &Path) -> Result<Option<DenoPermissions>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read Deno config: {}", path.display()))?;
+    Ok(parse_deno_permissions(&content, path))
+}
+
+fn parse_deno_permissions(content: &str, path: &Path) -> Option<DenoPermissions> {
+    let mut permissions = DenoPermissions {
+        config_file: path.to_path_buf(),
+        unrestricted: false,
+        granted: Vec::new(),
+    };
+    let mut found = false;
+
+    for (line_num, line) in content.lines().enumerate() {
+        if line.trim_start().starts_with("//") {
+            continue;
+        }
+        for cap in ALLOW_ENV.captures_iter(line) {
+            found = true;
+            let Some(names) = cap.name("names") else {
+                permissions.unrestricted = true;
+                continue;
+            };
+            for name in names.as_str().split(',').filter(|n| !n.is_empty()) {
+                if permissions.granted.iter().all(|g| g.name != name) {
+                    permissions.granted.push(EnvVarDefinition {
+                        name: name.to_string(),
+                        value: None,
+                        source_file: path.to_path_buf(),
+                        line: line_num + 1,
+                        environment: None,
+                    });
+                }
+            }
+        }
+    }
+
+    found.then_some(permissions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(content: &str) -> Option<DenoPermissions> {
+        parse_deno_permissions(content, Path::new("deno.json"))
+    }
+
+    #[test]
+    fn test_allow_env_list() {
+        let permissions = parse(
+            r#"{
+  "tasks": {
+    "start": "deno run --allow-net --allow-env=PORT,DATABASE_URL main.ts",
+    "dev": "deno run --watch --allow-env=PORT,AWS_* main.ts"
+  }
+}"#,
+        )
+        .unwrap();
+        assert!(!permissions.unrestricted);
+        let names: Vec<_> = permissions
+            .granted
+            .iter()
+            .map(|g| g.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["PORT", "DATABASE_URL", "AWS_*"]);
+        assert_eq!(permissions.granted[2].line, 4);
+    }
+
+    #[test]
+    fn test_unrestricted() {
+        let permissions = parse(r#"{ "tasks": { "start": "deno run -A main.ts" } }"#).unwrap();
+        assert!(permissions.unrestricted);
+    }
+
+    #[test]
+    fn test_no_env_permissions() {
+        assert!(parse(r#"{ "tasks": { "test": "deno test --allow-read" } }"#).is_none());
+    }
+}
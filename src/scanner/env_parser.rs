@@ -26,6 +26,7 @@ pub fn parse_env_file(path: &Path) -> Result<Vec<EnvVarDefinition>> {
                 value: Some(value),
                 source_file: path.to_path_buf(),
                 line: line_num,
+                environment: None,
             });
         }
     }
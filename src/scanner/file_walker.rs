@@ -3,6 +3,7 @@ use ignore::WalkBuilder;
 use std::path::{Path, PathBuf};
 
 use crate::config::ScanConfig;
+use crate::languages::TemplateScanner;
 use crate::types::Language;
 
 /// Walks through project files respecting .gitignore and config exclusions
@@ -10,24 +11,50 @@ pub struct FileWalker {
     root: PathBuf,
     exclude_patterns: Vec<String>,
     languages: Option<Vec<Language>>,
+    /// Extensions declared by custom scan patterns and template placeholders
+    extra_extensions: Vec<String>,
 }
 
 impl FileWalker {
     pub fn new(root: &Path, config: &ScanConfig) -> Self {
-        let languages = config
-            .languages
-            .as_ref()
-            .map(|langs| langs.iter().filter_map(|l| parse_language(l)).collect());
+        let languages = config.languages.as_ref().map(|langs| {
+            langs
+                .iter()
+                .filter_map(|l| Language::from_name(l))
+                .collect()
+        });
 
         Self {
             root: root.to_path_buf(),
             exclude_patterns: config.exclude.clone(),
             languages,
+            extra_extensions: config
+                .patterns
+                .iter()
+                .flat_map(|p| p.extensions.iter())
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .chain(
+                    TemplateScanner::from_scan_config(config)
+                        .iter()
+                        .flat_map(|s| s.file_extensions().to_vec()),
+                )
+                .collect(),
         }
     }
 
     /// Find all source code files to scan
     pub fn find_source_files(&self) -> Result<Vec<PathBuf>> {
+        self.find_files(|path| {
+            path.extension()
+                .and_then(|e| e.to_str())
+                .map(|ext| self.is_supported_extension(ext))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Find all non-excluded files accepted by `filter`, e.g. files supported
+    /// by a custom `LanguageRegistry`
+    pub fn find_files(&self, filter: impl Fn(&Path) -> bool) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
 
         let walker = WalkBuilder::new(&self.root)
@@ -55,11 +82,8 @@ impl FileWalker {
                 continue;
             }
 
-            // Check if file is a supported language
-            if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if self.is_supported_extension(ext) {
-                    files.push(path.to_path_buf());
-                }
+            if filter(path) {
+                files.push(path.to_path_buf());
             }
         }
 
@@ -134,10 +158,14 @@ impl FileWalker {
     fn is_supported_extension(&self, ext: &str) -> bool {
         let ext_lower = ext.to_lowercase();
 
+        if self.extra_extensions.contains(&ext_lower) {
+            return true;
+        }
+
         // Get list of allowed languages
         let languages = match &self.languages {
             Some(langs) => langs.clone(),
-            None => all_languages(),
+            None => Language::all(),
         };
 
         // Check if extension matches any allowed language
@@ -155,36 +183,7 @@ impl FileWalker {
 #[allow(dead_code)]
 pub fn get_language_for_file(path: &Path) -> Option<Language> {
     let ext = path.extension()?.to_str()?.to_lowercase();
-    all_languages()
+    Language::all()
         .into_iter()
         .find(|lang| lang.extensions().contains(&ext.as_str()))
 }
-
-fn all_languages() -> Vec<Language> {
-    vec![
-        Language::JavaScript,
-        Language::TypeScript,
-        Language::Python,
-        Language::Rust,
-        Language::Go,
-        Language::Ruby,
-        Language::Php,
-        Language::Java,
-        Language::CSharp,
-    ]
-}
-
-fn parse_language(name: &str) -> Option<Language> {
-    match name.to_lowercase().as_str() {
-        "javascript" | "js" => Some(Language::JavaScript),
-        "typescript" | "ts" => Some(Language::TypeScript),
-        "python" | "py" => Some(Language::Python),
-        "rust" | "rs" => Some(Language::Rust),
-        "go" => Some(Language::Go),
-        "ruby" | "rb" => Some(Language::Ruby),
-        "php" => Some(Language::Php),
-        "java" => Some(Language::Java),
-        "csharp" | "cs" | "c#" => Some(Language::CSharp),
-        _ => None,
-    }
-}
@@ -1,7 +1,13 @@
+mod client;
 mod code_scanner;
+mod deno_config;
 mod env_parser;
 mod file_walker;
+mod wrangler;
 
+pub use client::{find_client_files, parse_package_json, parse_public_prefixes, PACKAGE_JSON};
 pub use code_scanner::CodeScanner;
+pub use deno_config::{parse_deno_config, DENO_CONFIG_FILES};
 pub use env_parser::parse_env_file;
 pub use file_walker::FileWalker;
+pub use wrangler::{is_dev_vars_file, parse_dev_vars, parse_wrangler_config, WRANGLER_CONFIG};
@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+use super::env_parser::parse_env_file;
+use crate::types::EnvVarDefinition;
+
+/// Wrangler config file name
+pub const WRANGLER_CONFIG: &str = "wrangler.toml";
+
+static KEY_VALUE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^["']?(?P<key>[A-Za-z_][A-Za-z0-9_]*)["']?\s*=\s*(?P<value>.*)$"#).unwrap()
+});
+
+// `vars = { A = "1", B = 2 }` under an [env.*] table
+static INLINE_VARS: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^vars\s*=\s*\{(?P<body>.*)\}").unwrap());
+
+static INLINE_ENTRY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"["']?(?P<key>[A-Za-z_][A-Za-z0-9_]*)["']?\s*=\s*(?P<value>"[^"]*"|'[^']*'|[^,]+)"#,
+    )
+    .unwrap()
+});
+
+/// Returns true for `.dev.vars` and per-environment `.dev.vars.<env>` files
+pub fn is_dev_vars_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n == ".dev.vars" || n.starts_with(".dev.vars."))
+}
+
+/// Environment a `.dev.vars.<env>` file applies to; None for `.dev.vars`
+fn dev_vars_environment(path: &Path) -> Option<&str> {
+    path.file_name()?
+        .to_str()?
+        .strip_prefix(".dev.vars.")
+        .filter(|env| !env.is_empty())
+}
+
+/// Parse a `.dev.vars` file, labelling its definitions with the environment
+/// named by a `.dev.vars.<env>` suffix
+pub fn parse_dev_vars(path: &Path) -> Result<Vec<EnvVarDefinition>> {
+    let mut definitions = parse_env_file(path)?;
+    if let Some(environment) = dev_vars_environment(path) {
+        for definition in &mut definitions {
+            definition.environment = Some(environment.to_string());
+        }
+    }
+    Ok(definitions)
+}
+
+/// Parse the vars and bindings a Worker gets from wrangler.toml. Vars from
+/// `[vars]` and `[env.<name>.vars]` keep the line they are declared on, so
+/// each environment shows up as its own definition, labelled with the
+/// environment name.
+pub fn parse_wrangler_config(path: &Path) -> Result<Vec<EnvVarDefinition>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read wrangler config: {}", path.display()))?;
+    Ok(parse_wrangler_vars(&content, path))
+}
+
+fn parse_wrangler_vars(content: &str, path: &Path) -> Vec<EnvVarDefinition> {
+    let mut definitions = Vec::new();
+    let mut section = String::new();
+
+    for (line_num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        // [vars], [env.production.vars], [[kv_namespaces]], ...
+        if line.starts_with('[') {
+            section = line
+                .trim_start_matches('[')
+                .split(']')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string();
+            continue;
+        }
+
+        // `env.production.vars` -> production
+        let environment = section
+            .strip_prefix("env.")
+            .and_then(|rest| rest.split('.').next())
+            .map(String::from);
+        let mut define = |name: &str, value: Option<String>| {
+            definitions.push(EnvVarDefinition {
+                name: name.to_string(),
+                value,
+                source_file: path.to_path_buf(),
+                line: line_num + 1,
+                environment: environment.clone(),
+            });
+        };
+
+        let is_vars =
+            section == "vars" || (section.starts_with("env.") && section.ends_with(".vars"));
+        if let Some(cap) = INLINE_VARS.captures(line) {
+            for entry in INLINE_ENTRY.captures_iter(&cap["body"]) {
+                define(&entry["key"], Some(strip_quotes(&entry["value"])));
+            }
+        } else if let Some(cap) = KEY_VALUE.captures(line) {
+            let key = &cap["key"];
+            let value = strip_quotes(&cap["value"]);
+            if is_vars {
+                define(key, Some(value));
+            } else if key == "binding"
+                || (key == "name" && section.ends_with("durable_objects.bindings"))
+            {
+                // KV, R2, D1, service and other bindings are read as env.BINDING
+                define(&value, None);
+            }
+        }
+    }
+
+    definitions
+}
+
+fn strip_quotes(value: &str) -> String {
+    let value = value.split(" #").next().unwrap_or(value).trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vars_per_environment() {
+        let content = r#"name = "api"
+main = "src/index.ts"
+
+[vars]
+API_HOST = "https://api.example.com"
+RETRIES = 3
+
+[env.production.vars]
+API_HOST = "https://prod.example.com" # public
+
+[env.staging]
+vars = { API_HOST = "https://staging.example.com", DEBUG = "true" }
+"#;
+        let definitions = parse_wrangler_vars(content, Path::new("wrangler.toml"));
+        let found: Vec<_> = definitions
+            .iter()
+            .map(|d| {
+                (
+                    d.name.as_str(),
+                    d.value.as_deref().unwrap(),
+                    d.line,
+                    d.environment.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("API_HOST", "https://api.example.com", 5, None),
+                ("RETRIES", "3", 6, None),
+                (
+                    "API_HOST",
+                    "https://prod.example.com",
+                    9,
+                    Some("production")
+                ),
+                (
+                    "API_HOST",
+                    "https://staging.example.com",
+                    12,
+                    Some("staging")
+                ),
+                ("DEBUG", "true", 12, Some("staging")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bindings() {
+        let content = r#"[[kv_namespaces]]
+binding = "CACHE"
+id = "abc"
+
+[[durable_objects.bindings]]
+name = "COUNTER"
+class_name = "Counter"
+
+[[env.production.r2_buckets]]
+binding = "ASSETS"
+bucket_name = "assets"
+"#;
+        let definitions = parse_wrangler_vars(content, Path::new("wrangler.toml"));
+        let names: Vec<_> = definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["CACHE", "COUNTER", "ASSETS"]);
+        assert_eq!(definitions[2].environment.as_deref(), Some("production"));
+    }
+
+    #[test]
+    fn test_dev_vars_file_names() {
+        assert!(is_dev_vars_file(Path::new("worker/.dev.vars")));
+        assert!(is_dev_vars_file(Path::new(".dev.vars.staging")));
+        assert!(!is_dev_vars_file(Path::new(".env")));
+    }
+
+    #[test]
+    fn test_dev_vars_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let staging = dir.path().join(".dev.vars.staging");
+        std::fs::write(&staging, "API_KEY=abc\n").unwrap();
+        let definitions = parse_dev_vars(&staging).unwrap();
+        assert_eq!(definitions[0].name, "API_KEY");
+        assert_eq!(definitions[0].environment.as_deref(), Some("staging"));
+
+        let local = dir.path().join(".dev.vars");
+        std::fs::write(&local, "API_KEY=abc\n").unwrap();
+        assert_eq!(parse_dev_vars(&local).unwrap()[0].environment, None);
+    }
+}
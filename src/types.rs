@@ -1,9 +1,9 @@
-use serde::Serialize;
-use std::path::PathBuf;
+use serde::{Serialize, Serializer};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 /// Supported programming languages for env var scanning
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Language {
     JavaScript,
     TypeScript,
@@ -14,24 +14,62 @@ pub enum Language {
     Php,
     Java,
     CSharp,
+    /// A language provided by a scanner registered outside this crate,
+    /// identified by a lowercase id such as "elixir"
+    Custom(&'static str),
 }
 
 impl Language {
     /// Returns the file extensions associated with this language
     pub fn extensions(&self) -> &'static [&'static str] {
         match self {
-            Language::JavaScript => &["js", "mjs", "cjs", "jsx"],
+            Language::JavaScript => &[
+                "js", "mjs", "cjs", "jsx", "vue", "svelte", "astro", "html", "htm",
+            ],
             Language::TypeScript => &["ts", "mts", "cts", "tsx"],
             Language::Python => &["py"],
             Language::Rust => &["rs"],
             Language::Go => &["go"],
-            Language::Ruby => &["rb"],
-            Language::Php => &["php"],
-            Language::Java => &["java"],
-            Language::CSharp => &["cs"],
+            Language::Ruby => &["rb", "erb", "yml", "yaml"],
+            Language::Php => &["php", "yaml", "yml"],
+            Language::Java => &["java", "properties", "yml", "yaml"],
+            Language::CSharp => &["cs", "json"],
+            // Custom scanners decide which files they handle
+            Language::Custom(_) => &[],
         }
     }
 
+    /// Parse a language from its config name or common abbreviation
+    pub fn from_name(name: &str) -> Option<Language> {
+        match name.to_lowercase().as_str() {
+            "javascript" | "js" => Some(Language::JavaScript),
+            "typescript" | "ts" => Some(Language::TypeScript),
+            "python" | "py" => Some(Language::Python),
+            "rust" | "rs" => Some(Language::Rust),
+            "go" => Some(Language::Go),
+            "ruby" | "rb" => Some(Language::Ruby),
+            "php" => Some(Language::Php),
+            "java" => Some(Language::Java),
+            "csharp" | "cs" | "c#" => Some(Language::CSharp),
+            _ => None,
+        }
+    }
+
+    /// Returns all built-in languages
+    pub fn all() -> Vec<Language> {
+        vec![
+            Language::JavaScript,
+            Language::TypeScript,
+            Language::Python,
+            Language::Rust,
+            Language::Go,
+            Language::Ruby,
+            Language::Php,
+            Language::Java,
+            Language::CSharp,
+        ]
+    }
+
     /// Returns a human-readable name for the language
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -44,8 +82,31 @@ impl Language {
             Language::Php => "PHP",
             Language::Java => "Java",
             Language::CSharp => "C#",
+            Language::Custom(id) => id,
         }
     }
+
+    /// Returns the identifier used in config files and serialized output
+    pub fn id(&self) -> &'static str {
+        match self {
+            Language::JavaScript => "javascript",
+            Language::TypeScript => "typescript",
+            Language::Python => "python",
+            Language::Rust => "rust",
+            Language::Go => "go",
+            Language::Ruby => "ruby",
+            Language::Php => "php",
+            Language::Java => "java",
+            Language::CSharp => "csharp",
+            Language::Custom(id) => id,
+        }
+    }
+}
+
+impl Serialize for Language {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.id())
+    }
 }
 
 /// Where an environment variable was found
@@ -70,6 +131,113 @@ pub struct EnvVarDefinition {
     pub value: Option<String>,
     pub source_file: PathBuf,
     pub line: usize,
+    /// Deployment environment the definition is scoped to, such as
+    /// `production` for wrangler `[env.production.vars]`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+}
+
+/// Env access granted to a Deno project by `--allow-env` in its deno.json
+#[derive(Debug, Clone, Serialize)]
+pub struct DenoPermissions {
+    /// The deno.json file; its directory scopes the permissions
+    pub config_file: PathBuf,
+    /// Some task runs with unrestricted env access (`--allow-env`, `-A`)
+    pub unrestricted: bool,
+    /// Names listed in `--allow-env=X,Y`; a trailing `*` grants a prefix
+    pub granted: Vec<EnvVarDefinition>,
+}
+
+/// A client-side framework that only exposes prefixed vars to the browser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClientFramework {
+    Vite,
+    CreateReactApp,
+    Next,
+}
+
+impl ClientFramework {
+    /// Parse a `[client] frameworks` entry
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "vite" => Some(Self::Vite),
+            "cra" | "create-react-app" => Some(Self::CreateReactApp),
+            "next" | "nextjs" => Some(Self::Next),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Vite => "Vite",
+            Self::CreateReactApp => "Create React App",
+            Self::Next => "Next.js",
+        }
+    }
+
+    /// Prefix a var needs to reach client code
+    pub fn public_prefix(&self) -> &'static str {
+        match self {
+            Self::Vite => "VITE_",
+            Self::CreateReactApp => "REACT_APP_",
+            Self::Next => "NEXT_PUBLIC_",
+        }
+    }
+
+    /// Vars the framework provides to client code without the prefix
+    pub fn builtin_vars(&self) -> &'static [&'static str] {
+        match self {
+            Self::Vite => &["MODE", "BASE_URL", "PROD", "DEV", "SSR"],
+            Self::CreateReactApp => &["NODE_ENV", "PUBLIC_URL"],
+            Self::Next => &["NODE_ENV"],
+        }
+    }
+}
+
+/// An app built with client-side frameworks, rooted at its package.json
+#[derive(Debug, Clone)]
+pub struct ClientApp {
+    pub root: PathBuf,
+    pub frameworks: Vec<ClientFramework>,
+}
+
+/// Project facts gathered from files other than env files and source code
+#[derive(Debug, Clone, Default)]
+pub struct ProjectInfo {
+    /// Directory the scan started from
+    pub root: PathBuf,
+    /// `--allow-env` lists from deno.json files
+    pub deno_permissions: Vec<DenoPermissions>,
+    /// Apps using client-side frameworks
+    pub client_apps: Vec<ClientApp>,
+    /// Source files whose code runs in the browser
+    pub client_files: HashSet<PathBuf>,
+    /// Generic public prefixes such as `PUBLIC_` that a detected framework uses
+    pub public_prefixes: HashSet<&'static str>,
+}
+
+impl ProjectInfo {
+    /// The client app a file belongs to, by the nearest package.json above it
+    pub fn client_app_for(&self, path: &Path) -> Option<&ClientApp> {
+        nearest_client_app(&self.client_apps, path)
+    }
+}
+
+/// The app whose root is the deepest ancestor of `path`
+pub fn nearest_client_app<'a>(apps: &'a [ClientApp], path: &Path) -> Option<&'a ClientApp> {
+    apps.iter()
+        .filter(|app| path.starts_with(&app.root))
+        .max_by_key(|app| app.root.components().count())
+}
+
+/// Who can read a var, as declared by the framework module it comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Exposure {
+    /// Server-side only, e.g. `$env/static/private` or `astro:env/server`
+    Private,
+    /// Shipped to the browser, e.g. `$env/dynamic/public` or `runtimeConfig.public`
+    Public,
 }
 
 /// An environment variable usage (from source code)
@@ -82,6 +250,21 @@ pub struct EnvVarUsage {
     pub language: Language,
     /// The surrounding code context
     pub context: Option<String>,
+    /// Fallback value applied by the code when the var is not set
+    pub default_value: Option<String>,
+    /// Whether the code requires the var to be set (None if it cannot be inferred)
+    pub required: Option<bool>,
+    /// Whether a framework env module hands the var to server code only or to
+    /// the browser too (None outside such modules)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exposure: Option<Exposure>,
+}
+
+impl EnvVarUsage {
+    /// Returns true if the code can run without this var being set
+    pub fn is_optional(&self) -> bool {
+        self.default_value.is_some() || self.required == Some(false)
+    }
 }
 
 /// Issue severity levels
@@ -115,6 +298,14 @@ pub enum IssueKind {
     InconsistentNaming,
     /// Env var is defined multiple times
     DuplicateDefinition,
+    /// Env var read is not granted by, or granted without need by, `--allow-env`
+    EnvPermission,
+    /// Client-side code reads a var without the framework's public prefix
+    MissingClientPrefix,
+    /// A secret is exposed to the browser through a public prefix
+    ExposedSecret,
+    /// A missing var and an unused definition look like the same name misspelt
+    PossibleTypo,
 }
 
 impl std::fmt::Display for IssueKind {
@@ -124,6 +315,10 @@ impl std::fmt::Display for IssueKind {
             IssueKind::UnusedEnvVar => write!(f, "Unused env var"),
             IssueKind::InconsistentNaming => write!(f, "Inconsistent naming"),
             IssueKind::DuplicateDefinition => write!(f, "Duplicate definition"),
+            IssueKind::EnvPermission => write!(f, "Env permission"),
+            IssueKind::MissingClientPrefix => write!(f, "Missing client prefix"),
+            IssueKind::ExposedSecret => write!(f, "Exposed secret"),
+            IssueKind::PossibleTypo => write!(f, "Possible typo"),
         }
     }
 }